@@ -0,0 +1,32 @@
+#!/usr/bin/env bash
+# Runs the Criterion benchmarks (`benches/ecosystem.rs`) against a named baseline.
+#
+#     ./bench.sh save [baseline] [filter]    store the baseline (named `main` by default)
+#     ./bench.sh [baseline] [filter]         compare with the baseline, and fail on a regression
+#
+# A benchmark has regressed when Criterion finds it significantly slower than the baseline, by
+# more than NOISE (0.05, that is 5%, by default).
+set -euo pipefail
+
+NOISE=${NOISE:-0.05}
+
+if [ "${1:-}" = "save" ]; then
+    shift
+    baseline=${1:-main}
+    exec cargo bench --bench ecosystem -- --save-baseline "$baseline" ${2:+"$2"}
+fi
+
+baseline=${1:-main}
+log=$(mktemp)
+trap 'rm -f "$log"' EXIT
+
+cargo bench --bench ecosystem -- --baseline "$baseline" --noise-threshold "$NOISE" ${2:+"$2"} \
+    | tee "$log"
+
+if grep -q "Performance has regressed" "$log"; then
+    echo
+    echo "Regressed against baseline '$baseline':"
+    grep -B 3 "Performance has regressed" "$log" | awk '/^[a-z_]+\// { print "  " $1 }'
+    exit 1
+fi
+echo "No regressions against baseline '$baseline'."
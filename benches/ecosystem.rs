@@ -0,0 +1,158 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use ecosystem_rust::animals::Species;
+use ecosystem_rust::herd::Layout;
+use ecosystem_rust::island::Island;
+
+const SEED: u64 = 2024;
+
+const GEOGRAPHY: [&str; 22] = [
+    "WWWWWWWWWWWWWWWWWWWWW",
+    "WHHHHHLLLLWWLLLLLLLWW",
+    "WHHHHHLLLLWWLLLLLLLWW",
+    "WHHHHHLLLLWWLLLLLLLWW",
+    "WWHHLLLLLLLWWLLLLLLLW",
+    "WWHHLLLLLLLWWLLLLLLLW",
+    "WWWWWWWWHWWWWLLLLLLLW",
+    "WHHHHHLLLLWWLLLLLLLWW",
+    "WHHHHHHHHHWWLLLLLLWWW",
+    "WHHHHHMMMMMLLLLLLLWWW",
+    "WHHHHHMMMMMLLLLLLLWWW",
+    "WHHHHHMMMMMLLLLLLLWWW",
+    "WHHHHHMMMMMWWLLLLLWWW",
+    "WHHHHMMMMMMLLLLWWWWWW",
+    "WWHHHHMMMMMMLWWWWWWWW",
+    "WWHHHHMMMMMLLLWWWWWWW",
+    "WHHHHHMMMMMLLLLLLLWWW",
+    "WHHHHMMMMMMLLLLWWWWWW",
+    "WWHHHHMMMMMLLLWWWWWWW",
+    "WWWHHHHLLLLLLLWWWWWWW",
+    "WWWHHHHHHWWWWWWWWWWWW",
+    "WWWWWWWWWWWWWWWWWWWWW",
+];
+
+// The 22x21 reference map, after the initial population has spread out over `years` years.
+fn settled(years: u16) -> Island<'static> {
+    let mut isl = Island::new(GEOGRAPHY.to_vec(), SEED).unwrap();
+    isl.add_population(vec![
+        ((4, 4), Species::Herbivore, 100),
+        ((4, 4), Species::Carnivore, 10)
+    ]).unwrap();
+    for _ in 0..years {
+        isl.yearly_cycle().unwrap();
+    }
+    isl
+}
+
+// An `n` by `n` lowland map surrounded by water.
+fn lowland(n: usize) -> Vec<String> {
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if i == 0 || j == 0 || i == n - 1 || j == n - 1 { 'W' } else { 'L' })
+                .collect()
+        })
+        .collect()
+}
+
+fn phases(c: &mut Criterion) {
+    let isl = settled(100);
+    let mut group = c.benchmark_group("phase");
+
+    group.bench_function("procreate", |b| {
+        b.iter_batched(|| isl.clone(), |mut isl| isl.procreate().unwrap(), BatchSize::LargeInput)
+    });
+    group.bench_function("feed", |b| {
+        b.iter_batched(|| isl.clone(), |mut isl| isl.feed().unwrap(), BatchSize::LargeInput)
+    });
+    group.bench_function("migrate", |b| {
+        b.iter_batched(|| isl.clone(), |mut isl| isl.migrate(), BatchSize::LargeInput)
+    });
+    group.bench_function("aging", |b| {
+        b.iter_batched(|| isl.clone(), |mut isl| isl.aging().unwrap(), BatchSize::LargeInput)
+    });
+
+    let landscape = isl.landscape();
+    let mut rng = StdRng::seed_from_u64(SEED);
+    for species in [Species::Herbivore, Species::Carnivore] {
+        group.bench_function(BenchmarkId::new("new_cell", species), |b| {
+            b.iter(|| isl.new_cell(&(4, 4), &species, &landscape[&species], &mut rng))
+        });
+    }
+    group.finish();
+}
+
+fn scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("map_size");
+    group.sample_size(10);
+    for n in [10, 20, 40] {
+        let geography = lowland(n);
+        let mut isl = Island::new(geography.iter().map(|row| row.as_str()).collect(), SEED).unwrap();
+        isl.add_population(vec![
+            ((n / 2, n / 2), Species::Herbivore, 100),
+            ((n / 2, n / 2), Species::Carnivore, 10)
+        ]).unwrap();
+        group.bench_function(BenchmarkId::from_parameter(n), |b| {
+            b.iter_batched(
+                || isl.clone(),
+                |mut isl| for _ in 0..20 { isl.yearly_cycle().unwrap() },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("population");
+    group.sample_size(20);
+    let geography = lowland(12);
+    for population in [100, 1000, 10000] {
+        let mut isl = Island::new(geography.iter().map(|row| row.as_str()).collect(), SEED).unwrap();
+        isl.add_population(vec![
+            ((6, 6), Species::Herbivore, population),
+            ((6, 6), Species::Carnivore, population / 10)
+        ]).unwrap();
+        group.bench_function(BenchmarkId::from_parameter(population), |b| {
+            b.iter_batched(|| isl.clone(), |mut isl| isl.yearly_cycle().unwrap(), BatchSize::LargeInput)
+        });
+    }
+    group.finish();
+}
+
+// Feeding in a single crowded cell, where predation has many herbivores to go through.
+fn dense(c: &mut Criterion) {
+    let mut isl = Island::new(vec!["WWW", "WLW", "WWW"], SEED).unwrap();
+    isl.add_population(vec![
+        ((1, 1), Species::Herbivore, 1000),
+        ((1, 1), Species::Carnivore, 50)
+    ]).unwrap();
+    c.bench_function("dense/feed", |b| {
+        b.iter_batched(|| isl.clone(), |mut isl| isl.feed().unwrap(), BatchSize::LargeInput)
+    });
+}
+
+// Aging with fitness recomputed per animal (`Structs`) or for the whole herd (`Columns`), on the
+// settled map and in a single crowded cell.
+fn layout(c: &mut Criterion) {
+    let settled = settled(100);
+    let mut crowded = Island::new(vec!["WWW", "WLW", "WWW"], SEED).unwrap();
+    crowded.add_population(vec![
+        ((1, 1), Species::Herbivore, 10000),
+        ((1, 1), Species::Carnivore, 1000)
+    ]).unwrap();
+
+    let mut group = c.benchmark_group("layout");
+    for (name, isl) in [("settled", &settled), ("crowded", &crowded)] {
+        for layout in [Layout::Structs, Layout::Columns] {
+            let mut isl = isl.clone();
+            isl.layout = layout;
+            group.bench_function(BenchmarkId::new(name, format!("{:?}", layout)), |b| {
+                b.iter_batched(|| isl.clone(), |mut isl| isl.aging().unwrap(), BatchSize::LargeInput)
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, phases, dense, layout, scaling);
+criterion_main!(benches);
@@ -1,7 +1,9 @@
+// The original implementation, kept as-is for comparison with `current`.
+#![allow(clippy::all, dead_code, unused_mut)]
+
 pub mod animals;
 pub mod island;
 mod graphics;
-mod benchmark;
 mod simulation;
 
 fn main() {
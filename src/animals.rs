@@ -1,8 +1,7 @@
 use std::fmt::{Display, Formatter};
-use lazy_static::lazy_static;
 use rand::Rng;
-use rand::rngs::ThreadRng;
 use rand_distr::{Distribution, LogNormal};
+use super::error::*;
 
 pub struct Parameters {
     pub w_birth: f32,
@@ -19,6 +18,10 @@ pub struct Parameters {
     pub xi: f32,
     pub omega: f32,
     pub hunger: f32,
+    // Share of `hunger` that can be satisfied by grazing; the rest is hunted.
+    pub grazing: f32,
+    // Share of `hunger` that can be satisfied by eating carcasses.
+    pub scavenging: f32,
     pub delta_phi_max: f32,
     pub stride: usize,
     pub procreate: f32,
@@ -42,13 +45,15 @@ impl Parameters {
         xi: 0.42,
         omega: 0.4,
         hunger: 20.0,
+        grazing: 1.0,
+        scavenging: 0.0,
         delta_phi_max: 10.0,
 
         stride: 1,
 
         procreate: 0.22 * (10.0 + 4.0),  // zeta * (w_birth + sigma_birth)
-        birth_mean: 2.228375090434909,   // log((w_birth^2) / sqrt(w_birth^2 + sigma_birth^2))
-        birth_std: 0.3852531701599264,   // sqrt(log(1 + (sigma_birth^2 / w_birth^2)))
+        birth_mean: 2.228_375_2,         // log((w_birth^2) / sqrt(w_birth^2 + sigma_birth^2))
+        birth_std: 0.385_253_16,         // sqrt(log(1 + (sigma_birth^2 / w_birth^2)))
     };
     pub const CARNIVORE: Parameters = Parameters {
         w_birth: 6.0,
@@ -65,34 +70,119 @@ impl Parameters {
         xi: 1.1,
         omega: 0.3,
         hunger: 70.0,
+        grazing: 0.0,
+        scavenging: 0.0,
         delta_phi_max: 10.0,
 
         stride: 3,
 
         procreate: 3.5 * (6.0 + 1.0),    // zeta * (w_birth + sigma_birth)
-        birth_mean: 1.7780599821339977,  // log((w_birth^2) / sqrt(w_birth^2 + sigma_birth^2))
-        birth_std: 0.16552635496534787,  // sqrt(log(1 + (sigma_birth^2 / w_birth^2)))
+        birth_mean: 1.778_06,            // log((w_birth^2) / sqrt(w_birth^2 + sigma_birth^2))
+        birth_std: 0.165_526_36,         // sqrt(log(1 + (sigma_birth^2 / w_birth^2)))
     };
-}
+    pub const OMNIVORE: Parameters = Parameters {
+        w_birth: 8.0,
+        mu: 0.3,
+        sigma_birth: 2.0,
+        beta: 0.3,
+        eta: 0.15,
+        a_half: 20.0,
+        phi_age: 0.3,
+        w_half: 5.0,
+        phi_weight: 0.2,
+        gamma: 0.6,
+        zeta: 2.0,
+        xi: 0.8,
+        omega: 0.35,
+        hunger: 40.0,
+        grazing: 0.5,
+        scavenging: 0.0,
+        delta_phi_max: 10.0,
+
+        stride: 2,
+
+        procreate: 2.0 * (8.0 + 2.0),    // zeta * (w_birth + sigma_birth)
+        birth_mean: 2.049_129_2,         // log((w_birth^2) / sqrt(w_birth^2 + sigma_birth^2))
+        birth_std: 0.246_220_68,         // sqrt(log(1 + (sigma_birth^2 / w_birth^2)))
+    };
+    pub const SCAVENGER: Parameters = Parameters {
+        w_birth: 7.0,
+        mu: 0.5,
+        sigma_birth: 1.5,
+        beta: 0.4,
+        eta: 0.1,
+        a_half: 30.0,
+        phi_age: 0.35,
+        w_half: 4.0,
+        phi_weight: 0.25,
+        gamma: 0.7,
+        zeta: 2.5,
+        xi: 1.0,
+        omega: 0.3,
+        hunger: 30.0,
+        grazing: 0.0,
+        scavenging: 1.0,
+        delta_phi_max: 10.0,
 
-lazy_static! {
-    static ref HERBIVORE_DISTRIBUTION: LogNormal<f32> =
-    LogNormal::new(
-        Parameters::HERBIVORE.birth_mean,
-        Parameters::HERBIVORE.birth_std,
-    ).unwrap();
-
-    static ref CARNIVORE_DISTRIBUTION: LogNormal<f32> =
-    LogNormal::new(
-        Parameters::CARNIVORE.birth_mean,
-        Parameters::CARNIVORE.birth_std,
-    ).unwrap();
+        stride: 2,
+
+        procreate: 2.5 * (7.0 + 1.5),    // zeta * (w_birth + sigma_birth)
+        birth_mean: 1.923_462_5,         // log((w_birth^2) / sqrt(w_birth^2 + sigma_birth^2))
+        birth_std: 0.211_885_16,         // sqrt(log(1 + (sigma_birth^2 / w_birth^2)))
+    };
+    pub const APEX: Parameters = Parameters {
+        w_birth: 12.0,
+        mu: 0.3,
+        sigma_birth: 2.0,
+        beta: 0.7,
+        eta: 0.1,
+        a_half: 50.0,
+        phi_age: 0.4,
+        w_half: 8.0,
+        phi_weight: 0.2,
+        gamma: 0.5,
+        zeta: 3.5,
+        xi: 1.2,
+        omega: 0.25,
+        hunger: 90.0,
+        grazing: 0.0,
+        scavenging: 0.0,
+        delta_phi_max: 10.0,
+
+        stride: 4,
+
+        procreate: 3.5 * (12.0 + 2.0),   // zeta * (w_birth + sigma_birth)
+        birth_mean: 2.471_207,           // log((w_birth^2) / sqrt(w_birth^2 + sigma_birth^2))
+        birth_std: 0.165_526_36,         // sqrt(log(1 + (sigma_birth^2 / w_birth^2)))
+    };
 }
 
-#[derive(Eq, PartialEq, Hash, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 pub enum Species {
     Herbivore,
-    Carnivore
+    Carnivore,
+    // Grazes like a herbivore for part of its hunger, and hunts herbivores for the rest.
+    Omnivore,
+    // Eats the carcasses left in a cell by deaths and predation.
+    Scavenger,
+    // Hunts the other hunters (see `foodweb::FoodWeb`).
+    Apex,
+}
+
+impl Species {
+    pub const ALL: [Species; 5] = [
+        Species::Herbivore, Species::Carnivore, Species::Omnivore, Species::Scavenger, Species::Apex
+    ];
+
+    pub fn parameters(&self) -> &'static Parameters {
+        match self {
+            Species::Herbivore => &Parameters::HERBIVORE,
+            Species::Carnivore => &Parameters::CARNIVORE,
+            Species::Omnivore => &Parameters::OMNIVORE,
+            Species::Scavenger => &Parameters::SCAVENGER,
+            Species::Apex => &Parameters::APEX,
+        }
+    }
 }
 
 impl Display for Species {
@@ -100,32 +190,50 @@ impl Display for Species {
         match self {
             Species::Herbivore => write!(f, "Herbivore"),
             Species::Carnivore => write!(f, "Carnivore"),
+            Species::Omnivore => write!(f, "Omnivore"),
+            Species::Scavenger => write!(f, "Scavenger"),
+            Species::Apex => write!(f, "Apex"),
         }
     }
 }
 
-pub fn birthweight(species: Species, rng: &mut ThreadRng) -> f32 {
-    let distribution = match species {
-        Species::Herbivore => &*HERBIVORE_DISTRIBUTION,
-        Species::Carnivore => &*CARNIVORE_DISTRIBUTION,
-    };
-    distribution.sample(rng)
+pub fn birthweight<R: Rng>(species: Species, rng: &mut R) -> Result<f32> {
+    let parameters = species.parameters();
+    let distribution = LogNormal::new(parameters.birth_mean, parameters.birth_std)
+        .map_err(|error| Error::InvalidParameter(format!("birthweight of {}: {}", species, error)))?;
+    Ok(distribution.sample(rng))
+}
+
+// Outcome of one animal's hunt.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct Hunt {
+    pub attempts: u32,
+    pub kills: u32,
+    // Food eaten of the kills, and biomass of the kills that was left as carcass.
+    pub eaten: f32,
+    pub leftovers: f32,
 }
 
-#[derive(Clone)]
+// Infection state of an animal (see `disease::Disease`).
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum Health {
+    Susceptible,
+    Infected,
+    Recovered,
+}
+
+#[derive(Clone, Debug)]
 pub struct Animal {
     pub species: Species,
     pub weight: f32,
     pub age: u32,
     pub fitness: f32,
+    pub health: Health,
 }
 
 impl Animal {
     pub fn eat(&mut self, food: f32) {
-        match self.species {
-            Species::Herbivore => self.weight += &Parameters::HERBIVORE.beta * food,
-            Species::Carnivore => self.weight += &Parameters::CARNIVORE.beta * food,
-        };
+        self.weight += self.species.parameters().beta * food;
         self.calculate_fitness();
     }
 
@@ -134,17 +242,11 @@ impl Animal {
     }
 
     pub fn lose_weight_year(&mut self) {
-        match self.species {
-            Species::Herbivore => self.weight -= &Parameters::HERBIVORE.eta * self.weight,
-            Species::Carnivore => self.weight -= &Parameters::CARNIVORE.eta * self.weight,
-        };
+        self.weight -= self.species.parameters().eta * self.weight;
     }
 
     pub fn lose_weight_birth(&mut self, baby_weight: f32) -> bool {
-        let xi: &f32 = match self.species {
-            Species::Herbivore => &Parameters::HERBIVORE.xi,
-            Species::Carnivore => &Parameters::CARNIVORE.xi,
-        };
+        let xi = self.species.parameters().xi;
         if self.weight > xi * baby_weight {
             self.weight -= xi * baby_weight;
             self.calculate_fitness();
@@ -160,20 +262,7 @@ impl Animal {
             return
         }
 
-        let (phi_age, a_half, phi_weight, w_half) = match self.species {
-            Species::Herbivore => (
-                &Parameters::HERBIVORE.phi_age,
-                &Parameters::HERBIVORE.a_half,
-                &Parameters::HERBIVORE.phi_weight,
-                &Parameters::HERBIVORE.w_half,
-            ),
-            Species::Carnivore => (
-                &Parameters::CARNIVORE.phi_age,
-                &Parameters::CARNIVORE.a_half,
-                &Parameters::CARNIVORE.phi_weight,
-                &Parameters::CARNIVORE.w_half,
-            ),
-        };
+        let Parameters { phi_age, a_half, phi_weight, w_half, .. } = self.species.parameters();
         let q_pos = (1.0
             + f32::exp(phi_age * (self.age as f32 - a_half)))
         .powf(-1.0);
@@ -185,52 +274,84 @@ impl Animal {
         self.fitness = q_pos * q_neg;
     }
 
-    pub fn graze(&mut self, available: f32) -> f32 {
-        match self.species {
-            Species::Carnivore => panic!("Carnivores can't graze!"),
-            _ => ()
+    // Eats of the `available` fodder, up to the grazing share of the hunger. Returns the amount
+    // eaten.
+    pub fn graze(&mut self, available: f32) -> Result<f32> {
+        let parameters = self.species.parameters();
+        if parameters.grazing == 0.0 {
+            return Err(Error::SpeciesMismatch { species: self.species, action: "graze" })
         }
 
-        if available >= Parameters::HERBIVORE.hunger {
-            self.eat(Parameters::HERBIVORE.hunger);
-            Parameters::HERBIVORE.hunger
+        let appetite = parameters.hunger * parameters.grazing;
+        if available >= appetite {
+            self.eat(appetite);
+            Ok(appetite)
         } else {
             self.eat(available);
-            available
+            Ok(available)
+        }
+    }
+
+    // Eats of the `available` carcass, up to the scavenging share of the hunger. Returns the
+    // amount eaten.
+    pub fn scavenge(&mut self, available: f32) -> Result<f32> {
+        let parameters = self.species.parameters();
+        if parameters.scavenging == 0.0 {
+            return Err(Error::SpeciesMismatch { species: self.species, action: "scavenge" })
         }
+
+        let eaten = available.min(parameters.hunger * parameters.scavenging);
+        self.eat(eaten);
+        Ok(eaten)
+    }
+
+    // Hunts `herbivores` to satisfy the whole hunger.
+    pub fn predation<R: Rng>(&mut self, rng: &mut R, herbivores: &mut Vec<Animal>) -> Result<Hunt> {
+        let parameters = self.species.parameters();
+        self.hunt(rng, herbivores, parameters.hunger, parameters.delta_phi_max)
     }
 
-    pub fn predation(&mut self, rng: &mut ThreadRng, herbivores: &mut Vec<Animal>) {
-        match self.species {
-            Species::Herbivore => panic!("Herbivores can't hunt!"),
-            _ => ()
+    // Hunts `prey` to eat at most `appetite`, such as what is left of the hunger after grazing.
+    // A hunt always succeeds when the fitness difference is at least `delta_phi_max`.
+    pub fn hunt<R: Rng>(
+        &mut self, rng: &mut R, prey: &mut Vec<Animal>, appetite: f32, delta_phi_max: f32
+    ) -> Result<Hunt> {
+        let parameters = self.species.parameters();
+        if parameters.grazing + parameters.scavenging >= 1.0 {
+            return Err(Error::SpeciesMismatch { species: self.species, action: "hunt" })
         }
 
-        let mut eaten: f32 = 0.0;
-        herbivores.retain(|herbivore| {
-            if self.fitness > herbivore.fitness {
-                let difference = self.fitness - herbivore.fitness;
-                let probability = if 0.0 < difference && difference < Parameters::CARNIVORE
-                    .delta_phi_max {
-                    difference / Parameters::CARNIVORE.delta_phi_max
+        let mut hunt = Hunt::default();
+        prey.retain(|victim| {
+            if self.fitness > victim.fitness {
+                hunt.attempts += 1;
+                let difference = self.fitness - victim.fitness;
+                let probability = if 0.0 < difference && difference < delta_phi_max {
+                    difference / delta_phi_max
                 } else {
                     1.0
                 };
                 if rng.gen::<f32>() < probability {
-                    let rest = Parameters::CARNIVORE.hunger - eaten;
+                    let rest = appetite - hunt.eaten;
                     if rest > 0.0 {
-                        let food = if herbivore.weight < rest {
-                            herbivore.weight
+                        let food = if victim.weight < rest {
+                            victim.weight
                         } else {
                             rest
                         };
-                        eaten += food;
+                        hunt.eaten += food;
+                        hunt.leftovers += victim.weight - food;
                         self.eat(food);
+                    } else {
+                        hunt.leftovers += victim.weight;
                     }
-                    return false;  // Remove the herbivore
+                    hunt.kills += 1;
+                    return false;  // Remove the prey
                 }
             }
-            true  // Keep the herbivore
+            true  // Keep the prey
         });
+        hunt.leftovers = hunt.leftovers.max(0.0);
+        Ok(hunt)
     }
 }
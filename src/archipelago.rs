@@ -0,0 +1,85 @@
+use std::collections::VecDeque;
+use indexmap::IndexMap;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use super::animals::*;
+use super::topology::*;
+
+// How a species crosses water. A migrating animal tries to cross with `probability`, and lands
+// on a random cell of another island at most `distance` cells away.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crossing {
+    pub probability: f32,
+    pub distance: usize,
+}
+
+impl Crossing {
+    pub const NONE: Crossing = Crossing { probability: 0.0, distance: 0 };
+}
+
+pub fn default_crossings() -> IndexMap<Species, Crossing> {
+    Species::ALL.iter().map(|species| (*species, Crossing::NONE)).collect()
+}
+
+// The islands of a map: connected components of land (every terrain except 'W').
+#[derive(Clone, Debug)]
+pub struct Archipelago {
+    ids: IndexMap<(usize, usize), usize>,
+    count: usize,
+}
+
+impl Archipelago {
+    pub fn new(geography: &[&[u8]], topology: &Topology) -> Archipelago {
+        let land = |(i, j): (usize, usize)| geography[i][j] != b'W';
+
+        let mut ids: IndexMap<(usize, usize), usize> = IndexMap::new();
+        let mut count = 0;
+        for (i, row) in geography.iter().enumerate() {
+            for j in 0..row.len() {
+                if !land((i, j)) || ids.contains_key(&(i, j)) {
+                    continue
+                }
+
+                // Flood fill the new island.
+                let mut queue = VecDeque::from([(i, j)]);
+                ids.insert((i, j), count);
+                while let Some(cell) = queue.pop_front() {
+                    for other in topology.adjacent(cell) {
+                        if land(other) && !ids.contains_key(&other) {
+                            ids.insert(other, count);
+                            queue.push_back(other);
+                        }
+                    }
+                }
+                count += 1;
+            }
+        }
+        Archipelago { ids, count }
+    }
+
+    // Island of `cell`, or `None` for water.
+    pub fn island(&self, cell: &(usize, usize)) -> Option<usize> {
+        self.ids.get(cell).copied()
+    }
+
+    // Number of islands.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // Random land cell of another island within `distance` of `origin`.
+    pub fn crossing(
+        &self,
+        origin: &(usize, usize),
+        distance: usize,
+        topology: &Topology,
+        rng: &mut StdRng,
+    ) -> Option<(usize, usize)> {
+        let home = self.island(origin);
+        let shores: Vec<(usize, usize)> = topology.within(*origin, distance)
+            .into_iter()
+            .filter(|cell| self.island(cell).is_some_and(|island| Some(island) != home))
+            .collect();
+        shores.choose(rng).copied()
+    }
+}
@@ -0,0 +1,54 @@
+use indexmap::IndexMap;
+use super::animals::*;
+use super::error::*;
+
+// Density-dependent regulation of one species. Births become rarer as a cell fills up towards
+// the carrying capacity of its terrain, and animals above the capacity may die of crowding.
+// Terrain without a capacity is not regulated.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Density {
+    // Number of animals that a cell of each terrain can carry.
+    pub capacity: IndexMap<char, f32>,
+    // Chance of dying of crowding for an animal in a cell that is full beyond its capacity; the
+    // chance is scaled by the share of the animals that are above the capacity.
+    pub crowding: f32,
+}
+
+impl Density {
+    pub fn validate(&self) -> Result<()> {
+        if self.capacity.values().any(|capacity| capacity.is_nan() || *capacity < 0.0) {
+            return Err(Error::InvalidParameter("carrying capacity must be non-negative".to_string()))
+        }
+        if !(0.0..=1.0).contains(&self.crowding) {
+            return Err(Error::InvalidParameter(format!("crowding {} is not in [0, 1]", self.crowding)))
+        }
+        Ok(())
+    }
+
+    pub fn capacity(&self, terrain: u8) -> Option<f32> {
+        self.capacity.get(&(terrain as char)).copied()
+    }
+
+    // Factor of the birth probability in a cell of `terrain` with `n` animals of the species.
+    pub fn birth(&self, terrain: u8, n: usize) -> f32 {
+        match self.capacity(terrain) {
+            Some(capacity) => (1.0 - n as f32 / capacity).max(0.0),
+            None => 1.0,
+        }
+    }
+
+    // Chance of dying of crowding in a cell of `terrain` with `n` animals of the species.
+    pub fn death(&self, terrain: u8, n: usize) -> f32 {
+        match self.capacity(terrain) {
+            Some(capacity) if n as f32 > capacity => self.crowding * (1.0 - capacity / n as f32),
+            _ => 0.0,
+        }
+    }
+}
+
+// Regulation of each species; none by default.
+pub fn default_density() -> IndexMap<Species, Density> {
+    Species::ALL.iter()
+        .map(|species| (*species, Density::default()))
+        .collect()
+}
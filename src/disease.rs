@@ -0,0 +1,83 @@
+use indexmap::IndexMap;
+use super::animals::*;
+use super::error::*;
+
+// SIR disease that spreads between the animals of a cell, and between cells with the animals
+// that carry it when migrating.
+#[derive(Clone, Debug)]
+pub struct Disease {
+    // Chance that one infected animal in the cell infects a fully susceptible animal in a year.
+    // The chance of infection grows with the number of infected animals in the cell:
+    // 1 - exp(-transmission * susceptibility * infected).
+    pub transmission: f32,
+    // Relative susceptibility of each species. Species that are left out are not infected.
+    pub susceptibility: IndexMap<Species, f32>,
+    // Chance of recovering each year.
+    pub recovery: f32,
+    // Share of the weight lost each year while infected.
+    pub virulence: f32,
+    // Chance each year that a recovered animal loses its immunity.
+    pub waning: f32,
+}
+
+impl Disease {
+    pub fn validate(&self) -> Result<()> {
+        let probabilities = [
+            ("recovery", self.recovery),
+            ("virulence", self.virulence),
+            ("waning", self.waning),
+        ];
+        if let Some((name, value)) = probabilities.iter().find(|(_, value)| !(0.0..=1.0).contains(value)) {
+            return Err(Error::InvalidParameter(format!("disease {} {} is not in [0, 1]", name, value)))
+        }
+        if self.transmission < 0.0 || self.susceptibility.values().any(|s| *s < 0.0) {
+            return Err(Error::InvalidParameter(
+                "disease transmission and susceptibility must be non-negative".to_string()
+            ))
+        }
+        Ok(())
+    }
+
+    // Chance that a susceptible animal of `species` is infected in a cell with `infected`
+    // infected animals.
+    pub fn infection(&self, species: &Species, infected: u32) -> f32 {
+        let susceptibility = self.susceptibility.get(species).copied().unwrap_or(0.0);
+        1.0 - f32::exp(-self.transmission * susceptibility * infected as f32)
+    }
+}
+
+impl Default for Disease {
+    fn default() -> Disease {
+        Disease {
+            transmission: 0.02,
+            susceptibility: Species::ALL.iter().map(|species| (*species, 1.0)).collect(),
+            recovery: 0.3,
+            virulence: 0.1,
+            waning: 0.05,
+        }
+    }
+}
+
+// State of an epidemic in one year: the number of animals in each state, and the new infections
+// and recoveries of the year.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct Epidemic {
+    pub susceptible: u32,
+    pub infected: u32,
+    pub recovered: u32,
+    pub infections: u64,
+    pub recoveries: u64,
+}
+
+impl Epidemic {
+    // Share of the animals that are infected.
+    pub fn prevalence(&self) -> f32 {
+        let total = self.susceptible + self.infected + self.recovered;
+        self.infected as f32 / total.max(1) as f32
+    }
+
+    // New infections per susceptible animal.
+    pub fn incidence(&self) -> f32 {
+        self.infections as f32 / (self.susceptible as u64 + self.infections).max(1) as f32
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt::{Display, Formatter};
+use plotters::drawing::DrawingAreaErrorKind;
+use super::animals::Species;
+use super::invariants;
+
+// Everything that can go wrong in the crate.
+#[derive(Debug)]
+pub enum Error {
+    // A coordinate outside of the map.
+    InvalidCoordinate((usize, usize)),
+    // Terrain that is unknown, or where animals can't be placed.
+    InvalidTerrain { cell: (usize, usize), terrain: char },
+    // A map that is empty, ragged, or not surrounded by water.
+    InvalidGeography(String),
+    // An action of a species that it cannot perform, such as a carnivore grazing.
+    SpeciesMismatch { species: Species, action: &'static str },
+    InvalidParameter(String),
+    InvalidData(String),
+    // Broken invariants found with `Island::checking`.
+    Invariant(invariants::Report),
+    Render(String),
+    Io(std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Display for Error {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> std::fmt::Result {
+        match self {
+            Error::InvalidCoordinate(cell) => write!(f, "Coordinate {:?} is outside of the map", cell),
+            Error::InvalidTerrain { cell, terrain } =>
+                write!(f, "Invalid terrain '{}' at {:?}", terrain, cell),
+            Error::InvalidGeography(reason) => write!(f, "Invalid geography: {}", reason),
+            Error::SpeciesMismatch { species, action } => write!(f, "{}s can't {}", species, action),
+            Error::InvalidParameter(reason) => write!(f, "Invalid parameter: {}", reason),
+            Error::InvalidData(reason) => write!(f, "Invalid data: {}", reason),
+            Error::Invariant(report) => write!(f, "{}", report),
+            Error::Render(reason) => write!(f, "Failed to render: {}", reason),
+            Error::Io(error) => write!(f, "IO error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Error {
+        Error::Io(error)
+    }
+}
+
+impl<E: std::error::Error + Send + Sync> From<DrawingAreaErrorKind<E>> for Error {
+    fn from(error: DrawingAreaErrorKind<E>) -> Error {
+        Error::Render(error.to_string())
+    }
+}
@@ -0,0 +1,110 @@
+use super::animals::*;
+use super::error::*;
+
+// `predator` hunts `prey`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Link {
+    pub predator: Species,
+    pub prey: Species,
+    // Fitness difference at which a hunt always succeeds.
+    pub delta_phi_max: f32,
+    // Relative chance of hunting this prey before the other prey of the predator.
+    pub preference: f32,
+}
+
+impl Link {
+    pub fn new(predator: Species, prey: Species) -> Link {
+        Link {
+            predator, prey,
+            delta_phi_max: predator.parameters().delta_phi_max,
+            preference: 1.0,
+        }
+    }
+}
+
+// Who hunts whom on the island.
+#[derive(Clone, Debug)]
+pub struct FoodWeb {
+    links: Vec<Link>,
+    order: Vec<Species>,
+}
+
+impl FoodWeb {
+    // The links must not form a cycle, and every predator must be able to hunt.
+    pub fn new(links: Vec<Link>) -> Result<FoodWeb> {
+        for link in links.iter() {
+            let parameters = link.predator.parameters();
+            if link.predator == link.prey {
+                return Err(Error::InvalidParameter(format!("{}s can't hunt themselves", link.prey)))
+            }
+            if parameters.grazing + parameters.scavenging >= 1.0 {
+                return Err(Error::SpeciesMismatch { species: link.predator, action: "hunt" })
+            }
+            if link.delta_phi_max <= 0.0 || link.preference <= 0.0 {
+                return Err(Error::InvalidParameter(format!(
+                    "{} hunting {}s needs a positive delta_phi_max and preference",
+                    link.predator, link.prey
+                )))
+            }
+        }
+
+        // Trophic levels, found by repeatedly raising every predator above its prey. Without
+        // cycles this settles within as many rounds as there are species.
+        let mut levels = [0; Species::ALL.len()];
+        let index = |species: Species| Species::ALL.iter().position(|s| *s == species).expect("Expected Species");
+        for _ in 0..=Species::ALL.len() {
+            let mut changed = false;
+            for link in links.iter() {
+                let level = levels[index(link.prey)] + 1;
+                if levels[index(link.predator)] < level {
+                    levels[index(link.predator)] = level;
+                    changed = true;
+                }
+            }
+            if !changed {
+                // Lower levels eat first, and scavengers eat last, of what the others left.
+                let mut order = Species::ALL.to_vec();
+                order.sort_by_key(|species| (species.parameters().scavenging > 0.0, levels[index(*species)]));
+                return Ok(FoodWeb { links, order })
+            }
+        }
+        Err(Error::InvalidParameter("the food web has a cycle".to_string()))
+    }
+
+    pub fn links(&self) -> &[Link] {
+        &self.links
+    }
+
+    // Links from `predator` to each of its prey.
+    pub fn prey(&self, predator: Species) -> Vec<&Link> {
+        self.links.iter().filter(|link| link.predator == predator).collect()
+    }
+
+    // Species in the order in which they feed.
+    pub fn order(&self) -> &[Species] {
+        &self.order
+    }
+
+    // Number of links in the longest food chain from `species` down to a species that does not
+    // hunt.
+    pub fn level(&self, species: Species) -> usize {
+        self.prey(species).iter()
+            .map(|link| self.level(link.prey) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Default for FoodWeb {
+    // Carnivores and omnivores hunt herbivores, and apex predators hunt all three, preferring
+    // carnivores.
+    fn default() -> FoodWeb {
+        FoodWeb::new(vec![
+            Link::new(Species::Carnivore, Species::Herbivore),
+            Link::new(Species::Omnivore, Species::Herbivore),
+            Link { preference: 2.0, ..Link::new(Species::Apex, Species::Carnivore) },
+            Link::new(Species::Apex, Species::Omnivore),
+            Link { preference: 0.5, ..Link::new(Species::Apex, Species::Herbivore) },
+        ]).expect("Expected a valid food web")
+    }
+}
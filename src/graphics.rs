@@ -1,6 +1,8 @@
 use indexmap::IndexMap;
 use plotters::prelude::*;
 use crate::animals::Species;
+use crate::error::*;
+use crate::topology::Topology;
 
 pub struct Colour {
     pub r: u8,
@@ -11,9 +13,17 @@ pub struct Colour {
 impl Colour {
     pub const HERBIVORE: Colour = Colour { r: 132, g: 191, b: 161 };
     pub const CARNIVORE: Colour = Colour { r: 242, g: 195, b: 143 };
+    pub const OMNIVORE: Colour = Colour { r: 176, g: 156, b: 206 };
+    pub const SCAVENGER: Colour = Colour { r: 140, g: 120, b: 110 };
+    pub const APEX: Colour = Colour { r: 196, g: 98, b: 98 };
     pub const BACKGROUND: Colour = Colour { r: 251, g: 250, b: 245 };
     pub const AXIS: Colour = Colour { r: 0, g: 0, b: 0 };
 
+    pub const WATER: Colour = Colour { r: 155, g: 193, b: 219 };
+    pub const LOWLAND: Colour = Colour { r: 190, g: 214, b: 150 };
+    pub const HIGHLAND: Colour = Colour { r: 222, g: 214, b: 160 };
+    pub const MOUNTAIN: Colour = Colour { r: 190, g: 182, b: 172 };
+
     pub fn colour(&self) -> RGBColor {
         RGBColor(self.r, self.g, self.b)
     }
@@ -24,9 +34,12 @@ pub struct Graphics {
 }
 
 impl Graphics {
-    pub fn graph(&self, data: &IndexMap<Species, Vec<u32>>) {
+    pub fn graph(&self, data: &IndexMap<Species, Vec<u32>>) -> Result<()> {
+        if data.values().all(|v| v.is_empty()) {
+            return Err(Error::Render("no population history to graph".to_string()))
+        }
         let root = BitMapBackend::new(&self.path, (1024, 768)).into_drawing_area();
-        root.fill(&Colour::BACKGROUND.colour()).expect("Failed to fill the drawing area");
+        root.fill(&Colour::BACKGROUND.colour())?;
 
         let max_x = data.values().map(|v| v.len()).max().unwrap_or(10) - 1;
         let max_y = data.values().flat_map(|v| v.iter()).max().unwrap_or(&0) + 10;
@@ -35,8 +48,7 @@ impl Graphics {
             .caption("Population dynamics", ("monospace", 40))
             .margin(5)
             .set_all_label_area_size(40)
-            .build_cartesian_2d(0..max_x, 0..max_y)
-            .expect("Failed to build the chart");
+            .build_cartesian_2d(0..max_x, 0..max_y)?;
 
         chart.configure_mesh()
             .x_labels(10) // Number of labels on the x-axis
@@ -44,15 +56,21 @@ impl Graphics {
             .disable_x_mesh() // Disable grid lines on the x-axis
             .disable_y_mesh()
             .label_style(("monospace", 15).into_font())// Disable grid lines on the y-axis
-            .draw()
-            .expect("Failed to draw the axes");
+            .draw()?;
 
 
         for (species, data) in data.iter() {
+            // Leave out species that never lived on the island.
+            if data.iter().all(|&n| n == 0) {
+                continue
+            }
             let style = ShapeStyle::from(
                 &match species {
                     Species::Herbivore => Colour::HERBIVORE.colour(),
                     Species::Carnivore => Colour::CARNIVORE.colour(),
+                    Species::Omnivore => Colour::OMNIVORE.colour(),
+                    Species::Scavenger => Colour::SCAVENGER.colour(),
+                    Species::Apex => Colour::APEX.colour(),
                 }
             ).stroke_width(2);
 
@@ -61,16 +79,75 @@ impl Graphics {
                     data.iter().enumerate()
                         .map(|(i, v)| (i, *v)),
                     style
-                ))
-                .expect("Failed to draw the series")
+                ))?
                 .label(species.to_string())
                 .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], style));
         }
         chart.configure_series_labels()
-            .border_style(&Colour::AXIS.colour())
-            .background_style(&Colour::BACKGROUND.colour())
+            .border_style(Colour::AXIS.colour())
+            .background_style(Colour::BACKGROUND.colour())
             .label_font(("monospace", 20))
-            .draw()
-            .expect("Failed to configure the labels");
+            .draw()?;
+        root.present()?;
+        Ok(())
+    }
+
+    // Draws the cells of the map with their terrain, and marks the cells where each species
+    // lives.
+    pub fn map(
+        &self,
+        path: &str,
+        topology: &Topology,
+        geography: &[&[u8]],
+        placement: &IndexMap<(usize, usize), IndexMap<Species, u32>>,
+    ) -> Result<()> {
+        let (width, height) = topology.extent();
+        let scale = 800.0 / width.max(height);
+        let size = ((width * scale) as u32 + 20, (height * scale) as u32 + 20);
+
+        let root = BitMapBackend::new(path, size).into_drawing_area();
+        root.fill(&Colour::BACKGROUND.colour())?;
+        let chart = ChartBuilder::on(&root)
+            .margin(10)
+            .build_cartesian_2d(0.0..width, 0.0..height)?;
+        let area = chart.plotting_area();
+
+        // Rows are counted from the top of the map.
+        let flip = |(x, y): (f64, f64)| (x, height - y);
+
+        for (i, row) in geography.iter().enumerate() {
+            for (j, terrain) in row.iter().enumerate() {
+                let colour = match terrain {
+                    b'L' => Colour::LOWLAND.colour(),
+                    b'H' => Colour::HIGHLAND.colour(),
+                    b'M' => Colour::MOUNTAIN.colour(),
+                    _ => Colour::WATER.colour(),
+                };
+                let outline = topology.outline((i, j)).into_iter().map(flip).collect::<Vec<_>>();
+                area.draw(&Polygon::new(outline, colour.filled()))?;
+            }
+        }
+
+        for (cell, species) in placement.iter() {
+            let (x, y) = flip(topology.centre(*cell));
+            // One dot per species, side by side.
+            let step = 0.8 / Species::ALL.len() as f64;
+            for (index, (species, n)) in species.iter().enumerate() {
+                let shift = (index as f64 + 0.5) * step - 0.4;
+                if *n == 0 {
+                    continue
+                }
+                let colour = match species {
+                    Species::Herbivore => Colour::HERBIVORE.colour(),
+                    Species::Carnivore => Colour::CARNIVORE.colour(),
+                    Species::Omnivore => Colour::OMNIVORE.colour(),
+                    Species::Scavenger => Colour::SCAVENGER.colour(),
+                    Species::Apex => Colour::APEX.colour(),
+                };
+                area.draw(&Circle::new((x + shift, y), (0.4 * step * scale) as i32, colour.filled()))?;
+            }
+        }
+        root.present()?;
+        Ok(())
     }
 }
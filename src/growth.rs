@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand_distr::StandardNormal;
+use super::vegetation::*;
+
+// Rule for how the fodder of a plant grows back each year. `capacity` is the most fodder that
+// the other plants of the cell leave room for, and is at most `stand.f_max`.
+pub trait GrowthModel: Send + Sync {
+    fn grow(&self, stand: &Stand, capacity: f32, rng: &mut StdRng) -> f32;
+}
+
+// Grows by `v_max`, less the share `alpha` of the fodder that is missing.
+pub struct Regrowth {
+    pub v_max: f32,
+    pub alpha: f32,
+}
+
+impl GrowthModel for Regrowth {
+    fn grow(&self, stand: &Stand, capacity: f32, _rng: &mut StdRng) -> f32 {
+        if stand.f_max == 0.0 || stand.fodder == capacity {
+            return stand.fodder.min(capacity)
+        }
+        let growth = self.v_max * (
+            1.0 - self.alpha
+                * (stand.f_max - stand.fodder) / stand.f_max
+        );
+        f32::min(capacity, stand.fodder + growth)
+    }
+}
+
+// Original BioSim rule: the fodder is fully restored every year.
+pub struct Reset;
+
+impl GrowthModel for Reset {
+    fn grow(&self, _stand: &Stand, capacity: f32, _rng: &mut StdRng) -> f32 {
+        capacity
+    }
+}
+
+// Logistic growth at `rate` towards the most fodder. Grazed-out plants grow back from a seed bank
+// of the share `seed` of the most fodder.
+pub struct Logistic {
+    pub rate: f32,
+    pub seed: f32,
+}
+
+impl GrowthModel for Logistic {
+    fn grow(&self, stand: &Stand, capacity: f32, _rng: &mut StdRng) -> f32 {
+        if stand.f_max == 0.0 {
+            return 0.0
+        }
+        let fodder = stand.fodder.max(self.seed * stand.f_max);
+        f32::min(capacity, fodder + self.rate * fodder * (1.0 - fodder / stand.f_max))
+    }
+}
+
+// Grows by the same amount every year.
+pub struct Linear {
+    pub rate: f32,
+}
+
+impl GrowthModel for Linear {
+    fn grow(&self, stand: &Stand, capacity: f32, _rng: &mut StdRng) -> f32 {
+        f32::min(capacity, stand.fodder + self.rate)
+    }
+}
+
+// Growth of `model` in a year of good or bad weather: the growth is scaled by a log-normal
+// factor with median 1, drawn for each cell and year.
+pub struct Weather {
+    pub model: Arc<dyn GrowthModel>,
+    pub sigma: f32,
+}
+
+impl GrowthModel for Weather {
+    fn grow(&self, stand: &Stand, capacity: f32, rng: &mut StdRng) -> f32 {
+        let grown = self.model.grow(stand, capacity, rng);
+        let factor = (self.sigma * rng.sample::<f32, _>(StandardNormal)).exp();
+        (stand.fodder + (grown - stand.fodder) * factor).clamp(0.0, capacity)
+    }
+}
+
+// Growth model of each plant, unless set for a terrain with `Island::set_growth`.
+pub fn default_growth(plant: Plant) -> Arc<dyn GrowthModel> {
+    match plant {
+        Plant::Grass => Arc::new(Regrowth { v_max: 800.0, alpha: 0.1 }),
+        Plant::Shrub => Arc::new(Regrowth { v_max: 150.0, alpha: 0.1 }),
+    }
+}
@@ -0,0 +1,75 @@
+use indexmap::IndexMap;
+use super::animals::*;
+use super::error::*;
+
+// When in the yearly cycle the animals are harvested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Season {
+    // After feeding, when the animals are at their heaviest.
+    Feed,
+    // After aging, from the animals that survived the year.
+    Aging,
+}
+
+// How many animals of a species are taken each year.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Policy {
+    // A fixed number of animals, or all of them if there are fewer.
+    Quota(u32),
+    // A share of the animals, rounded to the nearest animal.
+    Proportional(f32),
+}
+
+// Which animals are taken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selection {
+    Random,
+    // The heaviest animals first.
+    Heaviest,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Harvest {
+    pub policy: Policy,
+    pub selection: Selection,
+}
+
+impl Harvest {
+    // Number of animals taken when `available` animals may be harvested.
+    pub fn amount(&self, available: usize) -> usize {
+        match self.policy {
+            Policy::Quota(quota) => available.min(quota as usize),
+            Policy::Proportional(share) => ((share * available as f32).round() as usize).min(available),
+        }
+    }
+}
+
+// Harvest of each species, from the animals of all cells outside the protected zones.
+// Species that are left out are not harvested.
+#[derive(Clone, Debug)]
+pub struct Harvesting {
+    pub season: Season,
+    pub harvests: IndexMap<Species, Harvest>,
+    // Cells where no animals are taken.
+    pub protected: Vec<(usize, usize)>,
+}
+
+impl Harvesting {
+    pub fn validate(&self) -> Result<()> {
+        if let Some((species, share)) = self.harvests.iter()
+            .find_map(|(species, harvest)| match harvest.policy {
+                Policy::Proportional(share) if !(0.0..=1.0).contains(&share) => Some((species, share)),
+                _ => None,
+            }) {
+            return Err(Error::InvalidParameter(format!("{} harvest share {} is not in [0, 1]", species, share)))
+        }
+        Ok(())
+    }
+}
+
+// Animals of one species taken in the harvest of one year.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct Yield {
+    pub animals: u32,
+    pub biomass: f32,
+}
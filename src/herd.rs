@@ -0,0 +1,92 @@
+use std::cell::RefCell;
+use super::animals::*;
+
+// How the aging phase updates the animals of a cell. The animals are stored as a `Vec<Animal>` in
+// either case, and the other phases do not depend on this setting.
+//
+// `Structs` ages each animal and recomputes its fitness one animal at a time.
+// `Columns` copies weight and age of the animals into a scratch `Herd`, recomputes fitness for
+// the whole herd in one pass (the age factor is only evaluated once per age), and writes the
+// results back. See the `layout` group of `benches/ecosystem.rs` for how they compare.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Layout {
+    Structs,
+    Columns,
+}
+
+// Scratch copy of the animals of one species in one cell, used by the aging phase with
+// `Layout::Columns`. Column `i` of `weight`, `age` and `fitness` belongs to animal `i`.
+#[derive(Default)]
+pub struct Herd {
+    pub weight: Vec<f32>,
+    pub age: Vec<u32>,
+    pub fitness: Vec<f32>,
+
+    // Age-dependent factor of the fitness, indexed by age, and filled in as the ages are met.
+    // Ages are small integers, so the exponential is only evaluated once per age instead of once
+    // per animal.
+    q_age: Vec<f32>,
+}
+
+thread_local! {
+    // Scratch herd of each thread, so that its columns are not allocated anew for every cell.
+    static SCRATCH: RefCell<Herd> = RefCell::new(Herd::default());
+}
+
+impl Herd {
+    // Runs `work` with the scratch herd of the current thread.
+    pub fn scratch<R>(work: impl FnOnce(&mut Herd) -> R) -> R {
+        SCRATCH.with_borrow_mut(work)
+    }
+
+    pub fn gather(&mut self, animals: &[Animal]) {
+        self.weight.clear();
+        self.age.clear();
+        self.weight.extend(animals.iter().map(|animal| animal.weight));
+        self.age.extend(animals.iter().map(|animal| animal.age));
+        self.fitness.clear();
+        self.fitness.resize(animals.len(), 0.0);
+    }
+
+    pub fn scatter(&self, animals: &mut [Animal]) {
+        for (idx, animal) in animals.iter_mut().enumerate() {
+            animal.weight = self.weight[idx];
+            animal.age = self.age[idx];
+            animal.fitness = self.fitness[idx];
+        }
+    }
+
+    pub fn aging(&mut self) {
+        self.age.iter_mut().for_each(|age| *age += 1);
+    }
+
+    pub fn lose_weight_year(&mut self, parameters: &Parameters) {
+        let eta = parameters.eta;
+        self.weight.iter_mut().for_each(|weight| *weight -= eta * *weight);
+    }
+
+    // Batched counterpart of `Animal::calculate_fitness`.
+    pub fn calculate_fitness(&mut self, parameters: &Parameters) {
+        let oldest = self.age.iter().copied().max().unwrap_or(0) as usize;
+        self.q_age.clear();
+        self.q_age.resize(oldest + 1, f32::NAN);
+
+        let (phi_age, a_half) = (parameters.phi_age, parameters.a_half);
+        let (phi_weight, w_half) = (parameters.phi_weight, parameters.w_half);
+        let q_age = &mut self.q_age;
+        for ((fitness, &weight), &age) in self.fitness.iter_mut()
+            .zip(self.weight.iter())
+            .zip(self.age.iter()) {
+            if weight <= 0.0 {
+                *fitness = 0.0;
+                continue
+            }
+            let q_pos = &mut q_age[age as usize];
+            if q_pos.is_nan() {
+                *q_pos = (1.0 + f32::exp(phi_age * (age as f32 - a_half))).recip();
+            }
+            let q_neg = (1.0 + f32::exp(-phi_weight * (weight - w_half))).recip();
+            *fitness = *q_pos * q_neg;
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::{Display, Formatter};
+use super::animals::*;
+use super::island::*;
+use super::profile::*;
+use super::streams::*;
+use super::vegetation::*;
+
+// Largest difference allowed between the stored and the recalculated fitness, which may be
+// computed in a different order (see `herd::Herd`).
+const TOLERANCE: f32 = 1e-5;
+
+// A broken invariant of the state of an `Island`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    // `inhabited` does not list exactly the non-empty cells.
+    Inhabited { missing: Vec<(usize, usize)>, stale: Vec<(usize, usize)> },
+    Water { cell: (usize, usize), animals: usize },
+    Fodder { cell: (usize, usize), plant: Plant, fodder: f32, f_max: f32 },
+    Carcass { cell: (usize, usize), carcass: f32 },
+    Fitness { cell: (usize, usize), species: Species, weight: f32, age: u32, fitness: f32, expected: f32 },
+    // The population changed by something other than births, kills, deaths and harvest.
+    Total { before: u64, after: u64, births: u64, kills: u64, deaths: u64, harvested: u64 },
+}
+
+impl Display for Violation {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> std::fmt::Result {
+        match self {
+            Violation::Inhabited { missing, stale } =>
+                write!(f, "inhabited is missing {:?} and lists empty cells {:?}", missing, stale),
+            Violation::Water { cell, animals } =>
+                write!(f, "{} animals on water at {:?}", animals, cell),
+            Violation::Fodder { cell, plant, fodder, f_max } =>
+                write!(f, "{} fodder {} outside [0, {}] at {:?}", plant, fodder, f_max, cell),
+            Violation::Carcass { cell, carcass } =>
+                write!(f, "carcass {} at {:?}", carcass, cell),
+            Violation::Fitness { cell, species, weight, age, fitness, expected } =>
+                write!(f, "{} at {:?} (weight {}, age {}) has fitness {}, expected {}",
+                       species, cell, weight, age, fitness, expected),
+            Violation::Total { before, after, births, kills, deaths, harvested } =>
+                write!(f, "population went from {} to {}, but births - kills - deaths - harvested = {}",
+                       before, after, *births as i64 - *kills as i64 - *deaths as i64 - *harvested as i64),
+        }
+    }
+}
+
+// Violations found after one phase of one year.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub year: u16,
+    pub phase: Phase,
+    pub violations: Vec<Violation>,
+}
+
+impl Display for Report {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> std::fmt::Result {
+        write!(f, "{} invariant violation(s) after {} in year {}:",
+               self.violations.len(), self.phase, self.year)?;
+        for violation in self.violations.iter() {
+            write!(f, "\n  {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+// Number of animals on `island`.
+pub fn total(island: &Island) -> u64 {
+    island.cells().values()
+        .flat_map(|cell| cell.animals.values())
+        .map(|animals| animals.len() as u64)
+        .sum()
+}
+
+// Checks the state of `island` after a phase that started with `before` animals and produced
+// `counts`. Migration moves animals without changing their number.
+pub fn check(island: &Island, before: u64, counts: &Counts) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    let occupied: Vec<(usize, usize)> = island.cells().iter()
+        .filter(|(_, cell)| !cell.is_empty())
+        .map(|(coordinate, _)| *coordinate)
+        .collect();
+    if island.inhabited() != occupied.as_slice() {
+        violations.push(Violation::Inhabited {
+            missing: occupied.iter()
+                .filter(|cell| !island.inhabited().contains(cell))
+                .copied().collect(),
+            stale: island.inhabited().iter()
+                .filter(|cell| !occupied.contains(cell))
+                .copied().collect(),
+        });
+    }
+
+    for (&(i, j), cell) in island.cells().iter() {
+        if island.geography[i][j] == b'W' && !cell.is_empty() {
+            violations.push(Violation::Water {
+                cell: (i, j),
+                animals: cell.animals.values().map(|animals| animals.len()).sum(),
+            });
+        }
+
+        for (plant, stand) in cell.vegetation.stands.iter() {
+            if !(0.0..=stand.f_max).contains(&stand.fodder) {
+                violations.push(Violation::Fodder {
+                    cell: (i, j), plant: *plant, fodder: stand.fodder, f_max: stand.f_max
+                });
+            }
+        }
+        if !(cell.carcass >= 0.0 && cell.carcass.is_finite()) {
+            violations.push(Violation::Carcass { cell: (i, j), carcass: cell.carcass });
+        }
+
+        for animal in cell.animals.values().flatten() {
+            let mut expected = animal.clone();
+            expected.calculate_fitness();
+            if !(0.0..=1.0).contains(&animal.fitness)
+                || (animal.fitness - expected.fitness).abs() > TOLERANCE {
+                violations.push(Violation::Fitness {
+                    cell: (i, j),
+                    species: animal.species,
+                    weight: animal.weight,
+                    age: animal.age,
+                    fitness: animal.fitness,
+                    expected: expected.fitness,
+                });
+            }
+        }
+    }
+
+    let after = total(island);
+    if (after + counts.kills + counts.deaths + counts.harvested) != before + counts.births {
+        violations.push(Violation::Total {
+            before, after,
+            births: counts.births,
+            kills: counts.kills,
+            deaths: counts.deaths,
+            harvested: counts.harvested,
+        });
+    }
+    violations
+}
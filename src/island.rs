@@ -1,10 +1,38 @@
+use std::sync::Arc;
+use std::time::Instant;
 use indexmap::IndexMap;
 use ordered_float::OrderedFloat;
-use rand::prelude::ThreadRng;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
 use super::animals::*;
-
+use super::archipelago::*;
+use super::density::*;
+use super::disease::*;
+use super::error::*;
+use super::foodweb::*;
+use super::growth::*;
+use super::harvest::*;
+use super::herd::*;
+use super::invariants;
+use super::migration::*;
+use super::mortality::*;
+use super::topology::*;
+use super::profile::*;
+use super::streams::*;
+use super::vegetation::*;
+
+// Number of animals of each species in each cell.
+pub type Census = IndexMap<(usize, usize), IndexMap<Species, u32>>;
+
+// Species and index of a migrating animal, and where it moves to.
+type Move = (Species, usize, Option<(usize, usize)>);
+
+// Cell and index of an animal that may be harvested, and its key in the order of the harvest.
+type Candidate = ((usize, usize), usize, f32);
+
+#[derive(Clone)]
 pub struct Island<'a> {
     pub year: u16,
     pub geography: Vec<&'a [u8]>,
@@ -12,379 +40,955 @@ pub struct Island<'a> {
     cells: IndexMap<(usize, usize), Cell>,
     inhabited: Vec<(usize, usize)>,
 
-    pub rng: &'a mut ThreadRng,
+    // How the aging phase recomputes fitness (see `herd::Layout`).
+    pub layout: Layout,
+
+    // Where migrating animals of each species move to; `Propensity` for species that are left out.
+    pub migration: IndexMap<Species, Arc<dyn MigrationStrategy>>,
+    // How cells are connected, for migration and drawing.
+    pub topology: Topology,
+    // Connected land masses of `geography`.
+    pub archipelago: Archipelago,
+    // How each species crosses the water between islands; species that are left out don't.
+    pub crossing: IndexMap<Species, Crossing>,
+    // Who hunts whom.
+    pub food_web: FoodWeb,
+    // Fodder that spreads between adjacent cells, and land that is degraded by overgrazing.
+    pub dispersal: Option<Dispersal>,
+    pub degradation: Option<Degradation>,
+    // Density-dependent birth and death of each species; species that are left out are not
+    // regulated.
+    pub density: IndexMap<Species, Density>,
+    // Age-dependent death of each species, on top of the fitness-dependent death. Species that
+    // are left out only die of weakness, as set by their `Parameters`.
+    pub mortality: IndexMap<Species, Mortality>,
+    // Number of animals of each species that died of age or weakness in the aging phase, by age.
+    // Animals that are killed by predators are not counted.
+    ages_at_death: IndexMap<Species, Vec<u64>>,
+    // Disease that spreads among the animals each year, after migration. Seed an outbreak with
+    // `infect`.
+    pub disease: Option<Disease>,
+    // Events of the last disease phase.
+    cases: Counts,
+    // Animals taken out of the island each year, after feeding or aging.
+    pub harvesting: Option<Harvesting>,
+    // Yield of the harvest of the current year; empty when nothing was harvested.
+    yields: IndexMap<Species, Yield>,
+
+    // Run the per-cell work of each phase on the rayon thread pool.
+    // Every cell draws from its own stream (see `streams::stream`), so this does not change the
+    // outcome of a seeded run.
+    pub parallel: bool,
+
+    // Accumulate wall time and event counts of each phase into `profile`.
+    pub profiling: bool,
+    pub profile: Profile,
+
+    // Check the invariants of the island after every phase (see `invariants::check`), and return
+    // `Error::Invariant` with a report of the violations. Meant for debugging, as it visits every
+    // animal.
+    pub checking: bool,
+    // Events of the last phase.
+    counts: Counts,
+
+    // Share of the decayed carcass biomass of a cell that becomes fodder, up to `f_max`.
+    pub enrichment: f32,
+
+    pub seed: u64,
+    pub rng: StdRng,
 }
 
 impl Island<'_> {
-    pub fn new<'a>(geography: Vec<&'a str>, rng: &'a mut ThreadRng) -> Island<'a> {
-
-        // Change `geography` into vector of bytes, and check that edges are 'W'.
-        let geography: Vec<&[u8]> = geography
-            .iter()
-            .map(|row| {
-                let row = row.as_bytes();
-                assert_eq!(*row.first().unwrap(), b'W', "Edges must be of 'W'");
-                assert_eq!(*row.last().unwrap(), b'W', "Edges must be of 'W'");
-                row
+    pub fn new(geography: Vec<&str>, seed: u64) -> Result<Island<'_>> {
+        Island::with_topology(geography, seed, Grid::Square4, false)
+    }
+
+    // Island on a `grid` of the given shape.
+    // Hexagonal maps are given in offset-row format, where odd rows may be indented to show the
+    // half-cell shift; the indentation is not part of the row, and all rows must be of the same
+    // length. Rows of other grids, and even rows of hexagonal maps, may not be indented.
+    // Toroidal maps wrap around at the edges, and need no border of 'W'.
+    pub fn with_topology(
+        geography: Vec<&str>, seed: u64, grid: Grid, toroidal: bool
+    ) -> Result<Island<'_>> {
+
+        // Change `geography` into vector of bytes, and check that it is rectangular with edges of
+        // 'W'. Only the odd rows of hexagonal maps may be indented.
+        let geography: Vec<&[u8]> = geography.iter().enumerate()
+            .map(|(i, row)| match grid {
+                Grid::Hexagonal if i % 2 == 1 => row.trim_start().as_bytes(),
+                _ => row.as_bytes(),
             })
             .collect();
-        if !geography.first().unwrap().iter().all(|&b| b == b'W') {
-            panic!("Edges must be of 'W'!")
+        let columns = geography.first().map_or(0, |row| row.len());
+        if columns == 0 {
+            return Err(Error::InvalidGeography("the map is empty".to_string()))
+        }
+        if geography.iter().any(|row| row.len() != columns) {
+            return Err(Error::InvalidGeography("rows must be of equal length".to_string()))
         }
-        if !geography.last().unwrap().iter().all(|&b| b == b'W') {
-            panic!("Edges must be of 'W'!")
+        let rows = geography.len();
+        let edge = |i: usize, j: usize| i == 0 || j == 0 || i == rows - 1 || j == columns - 1;
+        if !toroidal && geography.iter().enumerate()
+            .any(|(i, row)| row.iter().enumerate().any(|(j, &b)| edge(i, j) && b != b'W')) {
+            return Err(Error::InvalidGeography("edges must be of 'W'".to_string()))
         }
+        let topology = Topology::new(grid, toroidal, (rows, columns))?;
+        let archipelago = Archipelago::new(&geography, &topology);
 
         let cells: IndexMap<(usize, usize), Cell> = geography
             .iter().enumerate()
             .flat_map(|(i, row)| {
                 row.iter().enumerate()
-                    .map(move |(j, _el)| {
-                        let f_max: f32 = match _el {
+                    .map(move |(j, &terrain)| {
+                        let f_max: f32 = match terrain {
                             b'W' => 0.0,
                             b'H' => 300.0,
                             b'L' => 800.0,
                             b'M' => 0.0,
-                            _ => panic!("Unknown terrain type!"),
+                            _ => return Err(Error::InvalidTerrain { cell: (i, j), terrain: terrain as char }),
                         };
-                        ((i, j), Cell {
-                            f_max,
-                            fodder: f_max,
-                            animals: IndexMap::from([
-                                (Species::Herbivore, Vec::new()),
-                                (Species::Carnivore, Vec::new())
-                            ])
-                        })
+                        let mut vegetation = Vegetation::default();
+                        vegetation.plant(Plant::Grass, f_max);
+                        Ok(((i, j), Cell {
+                            terrain,
+                            vegetation,
+                            carcass: 0.0,
+                            dead: Vec::new(),
+                            animals: Species::ALL.iter()
+                                .map(|species| (*species, Vec::new()))
+                                .collect(),
+                        }))
                     })
             })
-            .collect();
+            .collect::<Result<_>>()?;
         let inhabited = Vec::new();
 
-        Island {
+        Ok(Island {
             year: 0,
             geography, cells, inhabited,
-            rng
-        }
+            layout: Layout::Structs,
+            migration: default_strategies(),
+            topology, archipelago,
+            crossing: default_crossings(),
+            food_web: FoodWeb::default(),
+            dispersal: None,
+            degradation: None,
+            density: default_density(),
+            mortality: default_mortality(),
+            ages_at_death: IndexMap::new(),
+            disease: None,
+            cases: Counts::default(),
+            harvesting: None,
+            yields: IndexMap::new(),
+            parallel: false,
+            profiling: false,
+            profile: Profile::default(),
+            checking: false,
+            counts: Counts::default(),
+            enrichment: 0.0,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        })
     }
 
     // Input: vec![((x, y), Species, n), ...]
     // Where (x, y) is the coordinate
     // and Species, n the Species and number of individuals.
-    pub fn add_population(&mut self, population: Vec<((usize, usize), Species, u16)>) {
+    // Nothing is added if any of the coordinates are outside of the map.
+    pub fn add_population(&mut self, population: Vec<((usize, usize), Species, u16)>) -> Result<()> {
+        if let Some((coordinate, _, _)) = population.iter()
+            .find(|(coordinate, _, _)| !self.cells.contains_key(coordinate)) {
+            return Err(Error::InvalidCoordinate(*coordinate))
+        }
+        if let Some(&((i, j), _, _)) = population.iter()
+            .find(|((i, j), _, _)| self.geography[*i][*j] == b'W') {
+            return Err(Error::InvalidTerrain { cell: (i, j), terrain: 'W' })
+        }
         for (coordinate, species, amount) in population {
             let cell = self.cells.get_mut(&coordinate).expect("Expected Cell.");
             for _ in 0..amount {
                 let mut animal = Animal {
                     species,
                     age: 0,
-                    weight: birthweight(species, self.rng),
+                    weight: birthweight(species, &mut self.rng)?,
                     fitness: 0.0,
+                    health: Health::Susceptible,
                 };
                 animal.calculate_fitness();
                 cell.animals.get_mut(&species).expect("Expected animals.").push(animal);
             }
         }
         self.update_inhabited();
+        Ok(())
     }
 
-    fn procreate(&mut self) {
-        let procreation = IndexMap::from([
-            (Species::Herbivore, Parameters::HERBIVORE.procreate),
-            (Species::Carnivore, Parameters::CARNIVORE.procreate),
-        ]);
-
-        self.inhabited.iter()
-            .for_each(|coordinate| {
-                self.cells.get_mut(coordinate).expect("Expected Cell")
-                    .animals.iter_mut()
-                    .for_each(|(species, animals)| {
-                        let probability: f32 = match species {
-                            Species::Herbivore => Parameters::HERBIVORE.gamma * animals.len() as f32,
-                            Species::Carnivore => Parameters::CARNIVORE.gamma * animals.len() as f32,
-                        };
-                        let mut babies = animals.iter_mut()
-                            .filter_map(|animal| {
-                                if animal.weight < procreation[species] {
-                                    return None
-                                }
-                                if self.rng.gen::<f32>() >= animal.fitness * probability {
-                                    return None
-                                }
-
-                                let babyweight = birthweight(*species, self.rng);
-                                if !animal.lose_weight_birth(babyweight) {
-                                    return None
-                                }
-
-                                let mut baby = Animal {
-                                    species: species.clone(),
-                                    age: 0,
-                                    weight: babyweight,
-                                    fitness: 0.0
-                                };
-                                baby.calculate_fitness();
-                                return Some(baby)
-                            }).collect();
-                        animals.append(&mut babies);
-                    });
-            });
+    // Sets the most fodder of `plant` in every cell of `terrain`, and fills it up. Without this,
+    // lowland and highland only grow grass.
+    pub fn set_vegetation(&mut self, terrain: char, plant: Plant, f_max: f32) -> Result<()> {
+        if !(f_max >= 0.0 && f_max.is_finite()) {
+            return Err(Error::InvalidParameter(format!("{} f_max {} on '{}'", plant, f_max, terrain)))
+        }
+        if terrain == 'W' {
+            return Err(Error::InvalidParameter(format!("{} can't grow in water", plant)))
+        }
+        let geography = &self.geography;
+        self.cells.iter_mut()
+            .filter(|((i, j), _)| geography[*i][*j] == terrain as u8)
+            .for_each(|(_, cell)| cell.vegetation.plant(plant, f_max));
+        Ok(())
     }
 
-    fn feed(&mut self) {
-        self.inhabited.iter()
-            .for_each(|coordinate| {
-                let cell = self.cells
-                    .get_mut(coordinate).expect("Expected Cell");
-
-                cell.grow_fodder();
-                if &cell.animals[&Species::Herbivore].len() > &0 {
-
-                    // Herbivores:
-                    cell.animals
-                        .get_mut(&Species::Herbivore).expect("Expected Herbivores")
-                        .sort_unstable_by_key(|herbivore| OrderedFloat(herbivore.fitness));
-
-                    for herbivore in cell.animals
-                        .get_mut(&Species::Herbivore).expect("Expected Herbivores")
-                        .iter_mut().rev() {
-                        cell.fodder -= herbivore.graze(cell.fodder);
-                        if cell.fodder == 0.0 {
-                            break;
-                        }
-                    }
-
-                    // Carnivores:
-                    cell.animals
-                        .get_mut(&Species::Carnivore).expect("Expected Carnivores")
-                        .shuffle(self.rng);
-                    let mut herbivores = cell.animals
-                        .get_mut(&Species::Herbivore).expect("Expected Herbivores")
-                        .clone();
-                    for carnivore in cell.animals
-                        .get_mut(&Species::Carnivore).expect("Expected Carnivores")
-                        .iter_mut() {
-                        carnivore.predation(self.rng, &mut herbivores);
-                        if herbivores.is_empty() {
-                            break;
-                        }
-                    }
-                    let _ = cell.animals.insert(Species::Herbivore, herbivores);
-                }
+    // Sets how `plant` grows in every cell of `terrain`.
+    pub fn set_growth(&mut self, terrain: char, plant: Plant, growth: Arc<dyn GrowthModel>) -> Result<()> {
+        if terrain == 'W' {
+            return Err(Error::InvalidParameter(format!("{} can't grow in water", plant)))
+        }
+        let geography = &self.geography;
+        self.cells.iter_mut()
+            .filter(|((i, j), _)| geography[*i][*j] == terrain as u8)
+            .for_each(|(_, cell)| {
+                cell.vegetation.stands.get_mut(&plant).expect("Expected Plant").growth = growth.clone();
             });
+        Ok(())
     }
 
-    fn migrate(&mut self) {
+    // Infects up to n susceptible animals of the species in each cell.
+    pub fn infect(&mut self, infections: Vec<((usize, usize), Species, u16)>) -> Result<()> {
+        if let Some((coordinate, _, _)) = infections.iter()
+            .find(|(coordinate, _, _)| !self.cells.contains_key(coordinate)) {
+            return Err(Error::InvalidCoordinate(*coordinate))
+        }
+        for (coordinate, species, amount) in infections {
+            self.cells.get_mut(&coordinate).expect("Expected Cell.")
+                .animals.get_mut(&species).expect("Expected animals.")
+                .iter_mut()
+                .filter(|animal| animal.health == Health::Susceptible)
+                .take(amount as usize)
+                .for_each(|animal| animal.health = Health::Infected);
+        }
+        Ok(())
+    }
 
-        // coordinate: (species: indices)
-        let mut migrating: IndexMap<(usize, usize), IndexMap<Species, Vec<usize>>> = IndexMap::new();
+    // Runs `work` on every inhabited cell, with the random stream of that cell for `phase`.
+    fn each_cell<F>(&mut self, phase: Phase, work: F) -> Result<Counts>
+    where
+        F: Fn(&mut Cell, &mut StdRng) -> Result<Counts> + Sync,
+    {
+        let (seed, year) = (self.seed, self.year);
+        let work = |(coordinate, cell): (&(usize, usize), &mut Cell)| {
+            if cell.is_empty() {
+                return Ok(Counts::default())
+            }
+            work(cell, &mut stream(seed, year, *coordinate, phase))
+        };
 
-        for coordinate in self.inhabited.iter() {
-            let cell = self.cells.get_mut(coordinate).expect("Expected Cell");
+        if self.parallel {
+            self.cells.par_iter_mut().map(work).try_reduce(Counts::default, |a, b| Ok(a + b))
+        } else {
+            self.cells.iter_mut().map(work).try_fold(Counts::default(), |a, b| Ok(a + b?))
+        }
+    }
 
-            migrating.insert(*coordinate, IndexMap::new());
-            for (species, animals) in cell.animals.iter_mut() {
-                migrating
-                    .get_mut(coordinate).expect("Expected coordinate")
-                    .insert(*species, Vec::new());
+    fn record(&mut self, counts: Counts) {
+        self.counts = counts;
+        if self.profiling {
+            self.profile.counts = self.profile.counts + counts;
+        }
+    }
 
-                let mu = match species {
-                    Species::Herbivore => Parameters::HERBIVORE.mu,
-                    Species::Carnivore => Parameters::CARNIVORE.mu,
-                };
-                for (idx, animal) in animals.iter_mut().enumerate() {
-                    if self.rng.gen::<f32>() > mu * animal.fitness {
-                        continue
-                    }
+    pub fn procreate(&mut self) -> Result<()> {
+        let density = self.density.clone();
+        for regulation in density.values() {
+            regulation.validate()?;
+        }
+        let counts = self.each_cell(Phase::Procreate, |cell, rng| cell.procreate(&density, rng))?;
+        self.record(counts);
+        Ok(())
+    }
 
-                    migrating
-                        .get_mut(coordinate).expect("Expected coordinate")
-                        .get_mut(species).expect("Expected species")
-                        .push(idx);
-                }
-            }
+    pub fn feed(&mut self) -> Result<()> {
+        if let Some(degradation) = &self.degradation {
+            degradation.validate()?;
         }
-        for (coordinate, moving) in migrating.iter() {
-            for (species, indices) in moving.iter() {
-                'moving: for idx in indices.iter().rev() {
-                    match self.new_cell(coordinate, species) {
-                        None => continue 'moving,
-                        Some(new_coordinate) => {
-                            let animal = self
-                                .cells.get_mut(coordinate).expect("Expected Cell")
-                                .animals.get_mut(species).expect("Expected Species")
-                                .remove(*idx);
-                            self.cells.get_mut(&new_coordinate).expect("Expected Cell")
-                                .animals.get_mut(species).expect("Expected Species")
-                                .push(animal);
-                        }
-                    }
-                }
+        if let Some(dispersal) = self.dispersal.clone() {
+            self.disperse(&dispersal)?;
+        }
+        let web = self.food_web.clone();
+        let counts = self.each_cell(Phase::Feed, |cell, rng| cell.feed(&web, rng))?;
+        self.record(counts);
+
+        if let Some(degradation) = self.degradation.clone() {
+            for cell in self.cells.values_mut() {
+                let grazed = cell.animals.iter()
+                    .any(|(species, animals)| species.parameters().grazing > 0.0 && !animals.is_empty());
+                cell.vegetation.degrade(&degradation, grazed);
             }
         }
-        self.update_inhabited();
+        Ok(())
     }
 
-    fn new_cell(&mut self, (x, y): &(usize, usize), species: &Species) -> Option<(usize, usize)> {
-
-        let (stride, hunger) = match species {
-            Species::Herbivore => (Parameters::HERBIVORE.stride, Parameters::HERBIVORE.hunger as u128),
-            Species::Carnivore => (Parameters::CARNIVORE.stride, Parameters::CARNIVORE.hunger as u128),
-        };
-
-        let x_range = x.saturating_sub(stride)..=x+stride;
-        let y_range = y.saturating_sub(stride)..=y+stride;
-
-        let possibilities: Vec<(usize, usize)> = x_range
-            .flat_map(|i| y_range.clone().map(move |j| (i, j)))
-            .filter(|&(i, j)| (i, j) != (*x, *y))
-            .filter(|&(i, j)| i < self.geography.len() && j < self.geography[0].len())
-            .filter(|&(i, j)| !self.geography[i][j].eq(&b'W'))
-            .filter(|&(i, j)| {
-                i.saturating_sub(*x).pow(2) + j.saturating_sub(*y).pow(2) <= stride.pow(2)
+    // Every plant grows by the share `rate` of its mean fodder in the adjacent land cells, from
+    // the fodder before any of them grew.
+    fn disperse(&mut self, dispersal: &Dispersal) -> Result<()> {
+        if !(dispersal.rate >= 0.0 && dispersal.rate.is_finite()) {
+            return Err(Error::InvalidParameter(format!("dispersal rate {}", dispersal.rate)))
+        }
+        let fodder: IndexMap<(usize, usize), Vec<f32>> = self.cells.iter()
+            .map(|(coordinate, cell)| {
+                (*coordinate, cell.vegetation.stands.values().map(|stand| stand.fodder).collect())
             })
             .collect();
+        for (&coordinate, cell) in self.cells.iter_mut() {
+            let neighbours: Vec<&Vec<f32>> = self.topology.adjacent(coordinate).iter()
+                .filter(|(i, j)| self.geography[*i][*j] != b'W')
+                .map(|neighbour| &fodder[neighbour])
+                .collect();
+            if neighbours.is_empty() {
+                continue
+            }
+            for (idx, stand) in cell.vegetation.stands.values_mut().enumerate() {
+                let mean = neighbours.iter().map(|fodder| fodder[idx]).sum::<f32>() / neighbours.len() as f32;
+                stand.fodder = f32::min(stand.f_max, stand.fodder + dispersal.rate * mean);
+            }
+        }
+        Ok(())
+    }
 
-        let mut propensities: Vec<f32> = possibilities
-            .iter()
-            .map(|&(i, j)| {
-                let cell = &self.cells[&(i, j)];
-                let fodder = match species {
-                    Species::Herbivore => cell.fodder,
-                    Species::Carnivore => cell
-                        .animals[&Species::Herbivore].iter()
-                        .map(|animal| animal.weight)
-                        .sum(),
+    // Migration is resolved in two steps. First every inhabited cell decides, independently and
+    // from the state at the start of the phase, which of its animals move where. Then the moves
+    // are applied in the (deterministic) order of `inhabited`.
+    pub fn migrate(&mut self) {
+        let (seed, year) = (self.seed, self.year);
+        let landscape = self.landscape();
+
+        let decide = |coordinate: &(usize, usize)| -> Vec<Move> {
+            let mut rng = stream(seed, year, *coordinate, Phase::Migrate);
+            let mut moves = Vec::new();
+            for (species, animals) in self.cells[coordinate].animals.iter() {
+                let mu = species.parameters().mu;
+                for (idx, animal) in animals.iter().enumerate() {
+                    if rng.gen::<f32>() > mu * animal.fitness {
+                        continue
+                    }
+                    let destination = self.new_cell(coordinate, species, &landscape[species], &mut rng);
+                    moves.push((*species, idx, destination));
+                }
+            }
+            moves
+        };
+        let migrating: Vec<Vec<Move>> = if self.parallel {
+            self.inhabited.par_iter().map(decide).collect()
+        } else {
+            self.inhabited.iter().map(decide).collect()
+        };
+
+        let mut counts = Counts::default();
+
+        // Emigrants are first taken out of every cell, and only placed in their new cells once
+        // all cells have been handled. An animal can therefore move at most once per year.
+        let mut arrivals: Vec<((usize, usize), Species, Animal)> = Vec::new();
+        for (coordinate, moves) in self.inhabited.iter().zip(migrating) {
+            // Indices are increasing per species, so removing in reverse keeps them valid.
+            for (species, idx, destination) in moves.into_iter().rev() {
+                counts.migration_attempts += 1;
+                let Some(destination) = destination else {
+                    continue
                 };
-                let population = cell.animals[species].len() as u128;
-                let abundance = fodder
-                    / (((population + 1) * hunger)
-                    .max(population + 1)
-                    .max(hunger)
-                    .max(1)) as f32;
-                abundance
-            })
-            .collect();
+                counts.migrations += 1;
 
-        if propensities.is_empty() {
-            return None;
+                let animal = self
+                    .cells.get_mut(coordinate).expect("Expected Cell")
+                    .animals.get_mut(&species).expect("Expected Species")
+                    .remove(idx);
+                arrivals.push((destination, species, animal));
+            }
         }
-
-        // Only consider the four best possibilities.
-        if propensities.len() > 4 {
-            propensities.sort_unstable_by_key(|&i| OrderedFloat(i));
-            propensities.drain(4..);
+        for (destination, species, animal) in arrivals {
+            self.cells.get_mut(&destination).expect("Expected Cell")
+                .animals.get_mut(&species).expect("Expected Species")
+                .push(animal);
         }
-        let propensity: f32 = propensities.iter().sum();
+        self.record(counts);
+        self.update_inhabited();
+    }
 
-        let chosen = self.rng.gen_range(0..propensities.len());
+    // Food and population of each species in each cell.
+    pub fn landscape(&self) -> IndexMap<Species, Landscape> {
+        Species::ALL.iter()
+            .map(|species| {
+                (*species, Landscape {
+                    food: self.cells.iter()
+                        .map(|(coordinate, cell)| (*coordinate, cell.food(species, &self.food_web)))
+                        .collect(),
+                    population: self.cells.iter()
+                        .map(|(coordinate, cell)| (*coordinate, cell.animals[species].len() as u32))
+                        .collect(),
+                })
+            })
+            .collect()
+    }
 
-        let probability: f32;
-        if propensity == 0.0f32 {
-            probability = 0.5;
-        } else {
-            probability = propensities[chosen] / propensity;
+    pub fn new_cell(
+        &self,
+        coordinate: &(usize, usize),
+        species: &Species,
+        landscape: &Landscape,
+        rng: &mut StdRng,
+    ) -> Option<(usize, usize)> {
+        let crossing = self.crossing.get(species).copied().unwrap_or(Crossing::NONE);
+        if crossing.probability > 0.0 && rng.gen::<f32>() < crossing.probability {
+            let shore = self.archipelago.crossing(coordinate, crossing.distance, &self.topology, rng);
+            if shore.is_some() {
+                return shore
+            }
         }
 
-        if self.rng.gen::<f32>() < probability {
-            return Some(possibilities[chosen])
+        let surroundings = Surroundings {
+            origin: *coordinate,
+            species: *species,
+            topology: &self.topology,
+            geography: &self.geography,
+            landscape,
         };
-        return None
+        match self.migration.get(species) {
+            Some(strategy) => strategy.destination(&surroundings, rng),
+            None => Propensity.destination(&surroundings, rng),
+        }
     }
 
     fn update_inhabited(&mut self) {
         self.inhabited = self.cells.iter()
-            .filter(|(_, cell)| {
-                !cell.animals[&Species::Herbivore].is_empty()
-                    || !cell.animals[&Species::Carnivore].is_empty()
-            })
+            .filter(|(_, cell)| !cell.is_empty())
             .map(|(coordinates, _cell)| {
                 *coordinates
             })
             .collect();
     }
 
-    fn aging(&mut self) {
-        self.inhabited.iter()
-            .for_each(|coordinate| {
-                self.cells.get_mut(coordinate).expect("Expected Cell")
-                    .animals.iter_mut()
-                    .for_each(|(species, animals)| {
-                        let omega = match species {
-                            Species::Herbivore => Parameters::HERBIVORE.omega,
-                            Species::Carnivore => Parameters::CARNIVORE.omega,
-                        };
-                        animals.retain_mut(|animal| {
-                            animal.aging();
-                            animal.lose_weight_year();
-                            animal.calculate_fitness();
-                            if animal.weight <= 0.0f32
-                                ||
-                                self.rng.gen::<f32>() < omega * (1.0f32 - animal.fitness) {
-                                false
-                            } else {
-                                true
-                            }
-                        });
-                    });
-            });
+    pub fn aging(&mut self) -> Result<()> {
+        let layout = self.layout;
+        let mortality = self.mortality.clone();
+        for rule in mortality.values() {
+            rule.validate()?;
+        }
+        let density = self.density.clone();
+        for regulation in density.values() {
+            regulation.validate()?;
+        }
+        let counts = self.each_cell(Phase::Aging, |cell, rng| Ok(cell.aging(layout, &mortality, &density, rng)))?;
+        self.record(counts);
+        self.update_inhabited();
+
+        for cell in self.cells.values_mut() {
+            for (species, age) in cell.dead.drain(..) {
+                let ages = self.ages_at_death.entry(species).or_default();
+                if ages.len() <= age as usize {
+                    ages.resize(age as usize + 1, 0);
+                }
+                ages[age as usize] += 1;
+            }
+        }
+
+        // Carcasses also rot in cells that are left empty.
+        let enrichment = self.enrichment;
+        self.cells.values_mut()
+            .filter(|cell| cell.carcass > 0.0)
+            .for_each(|cell| cell.decay(enrichment));
+        Ok(())
+    }
+
+    pub fn spread_disease(&mut self) -> Result<()> {
+        let Some(disease) = self.disease.clone() else {
+            return Ok(())
+        };
+        disease.validate()?;
+        let counts = self.each_cell(Phase::Disease, |cell, rng| Ok(cell.spread(&disease, rng)))?;
+        self.cases = counts;
+        self.record(counts);
+        Ok(())
+    }
+
+    // Takes the animals of each harvested species out of the cells outside the protected zones.
+    pub fn harvest(&mut self) -> Result<()> {
+        let Some(harvesting) = self.harvesting.clone() else {
+            return Ok(())
+        };
+        harvesting.validate()?;
+        if let Some(coordinate) = harvesting.protected.iter()
+            .find(|coordinate| !self.cells.contains_key(*coordinate)) {
+            return Err(Error::InvalidCoordinate(*coordinate))
+        }
+
+        // The key is a random number drawn from the stream of the cell, or the weight for the
+        // heaviest first.
+        let (seed, year) = (self.seed, self.year);
+        let mut candidates: IndexMap<Species, Vec<Candidate>> = IndexMap::new();
+        for coordinate in self.inhabited.iter().filter(|coordinate| !harvesting.protected.contains(coordinate)) {
+            let mut rng = stream(seed, year, *coordinate, Phase::Harvest);
+            for (species, harvest) in harvesting.harvests.iter() {
+                let animals = &self.cells[coordinate].animals[species];
+                let keys = candidates.entry(*species).or_default();
+                for (index, animal) in animals.iter().enumerate() {
+                    let key = match harvest.selection {
+                        Selection::Random => rng.gen::<f32>(),
+                        Selection::Heaviest => -animal.weight,
+                    };
+                    keys.push((*coordinate, index, key));
+                }
+            }
+        }
+
+        let mut counts = Counts::default();
+        for (species, harvest) in harvesting.harvests.iter() {
+            let mut candidates = candidates.swap_remove(species).unwrap_or_default();
+            candidates.sort_by(|a, b| a.2.total_cmp(&b.2));
+            let amount = harvest.amount(candidates.len());
+
+            let mut taken: IndexMap<(usize, usize), Vec<usize>> = IndexMap::new();
+            for (coordinate, index, _) in candidates.into_iter().take(amount) {
+                taken.entry(coordinate).or_default().push(index);
+            }
+            let harvested = self.yields.entry(*species).or_default();
+            for (coordinate, mut indices) in taken {
+                indices.sort_unstable();
+                let animals = self.cells.get_mut(&coordinate).expect("Expected Cell.")
+                    .animals.get_mut(species).expect("Expected animals.");
+                let mut index = 0;
+                animals.retain(|animal| {
+                    let take = indices.binary_search(&index).is_ok();
+                    if take {
+                        harvested.animals += 1;
+                        harvested.biomass += animal.weight;
+                    }
+                    index += 1;
+                    !take
+                });
+            }
+            counts.harvested += amount as u64;
+        }
+        self.record(counts);
+        self.update_inhabited();
+        Ok(())
     }
 
-    pub fn yearly_cycle(&mut self) {
-        self.procreate();
-        self.feed();
-        self.migrate();
-        self.aging();
+    pub fn yearly_cycle(&mut self) -> Result<()> {
+        let season = self.harvesting.as_ref().map(|harvesting| harvesting.season);
+        self.yields.clear();
+        self.timed(Phase::Procreate, Island::procreate)?;
+        self.timed(Phase::Feed, Island::feed)?;
+        if season == Some(Season::Feed) {
+            self.timed(Phase::Harvest, Island::harvest)?;
+        }
+        self.timed(Phase::Migrate, |island| {
+            island.migrate();
+            Ok(())
+        })?;
+        if self.disease.is_some() {
+            self.timed(Phase::Disease, Island::spread_disease)?;
+        }
+        self.timed(Phase::Aging, Island::aging)?;
+        if season == Some(Season::Aging) {
+            self.timed(Phase::Harvest, Island::harvest)?;
+        }
 
         self.year += 1;
+        if self.profiling {
+            self.profile.years += 1;
+        }
+        Ok(())
+    }
+
+    fn timed(&mut self, phase: Phase, run: fn(&mut Self) -> Result<()>) -> Result<()> {
+        let before = self.checking.then(|| invariants::total(self));
+        self.counts = Counts::default();
+
+        if self.profiling {
+            let start = Instant::now();
+            run(self)?;
+            *self.profile.time.entry(phase).or_default() += start.elapsed();
+        } else {
+            run(self)?;
+        }
+
+        if let Some(before) = before {
+            let violations = invariants::check(self, before, &self.counts);
+            if !violations.is_empty() {
+                return Err(Error::Invariant(invariants::Report { year: self.year, phase, violations }))
+            }
+        }
+        Ok(())
     }
 
-    pub fn animals(&mut self) -> (
-        IndexMap<Species, u32>, IndexMap<(usize, usize), IndexMap<Species, u32>>
-    )  {
-        let mut h: u32 = 0;
-        let mut c: u32 = 0;
+    pub fn animals(&self) -> (IndexMap<Species, u32>, Census) {
+        let mut total: IndexMap<Species, u32> = Species::ALL.iter()
+            .map(|species| (*species, 0))
+            .collect();
         let mut hc: IndexMap<(usize, usize), IndexMap<Species, u32>> = IndexMap::new();
 
         for coordinate in self.inhabited.iter() {
             hc.insert(*coordinate, IndexMap::new());
             let _hc = hc.get_mut(coordinate).expect("Expected coordinate");
 
-            for (species, animals) in self.cells
-                .get_mut(coordinate).expect("Expected Cell")
-                .animals.iter() {
+            for (species, animals) in self.cells[coordinate].animals.iter() {
                 let n = animals.len() as u32;
-                match species {
-                    Species::Herbivore => { h += n; },
-                    Species::Carnivore => { c += n; },
-                };
+                *total.get_mut(species).expect("Expected species") += n;
                 _hc.insert(*species, n + _hc.get(species).unwrap_or(&0));
             }
         }
-        (IndexMap::from([
-            (Species::Herbivore, h),
-            (Species::Carnivore, c)
-        ]), hc)
+        (total, hc)
+    }
+
+    // Number of animals in each infection state, and the infections and recoveries of the last
+    // disease phase.
+    pub fn epidemic(&self) -> Epidemic {
+        let mut epidemic = Epidemic {
+            infections: self.cases.infections,
+            recoveries: self.cases.recoveries,
+            ..Epidemic::default()
+        };
+        for animal in self.inhabited.iter()
+            .flat_map(|coordinate| self.cells[coordinate].animals.values().flatten()) {
+            match animal.health {
+                Health::Susceptible => epidemic.susceptible += 1,
+                Health::Infected => epidemic.infected += 1,
+                Health::Recovered => epidemic.recovered += 1,
+            }
+        }
+        epidemic
+    }
+
+    // Histogram of the ages at which the animals of each species have died in the aging phase.
+    // See `mortality::mean_age` and `mortality::quantile`.
+    pub fn ages_at_death(&self) -> &IndexMap<Species, Vec<u64>> {
+        &self.ages_at_death
+    }
+
+    // Number and biomass of the animals of each species taken in the harvest of this year, or
+    // of the last year after `yearly_cycle`.
+    pub fn harvest_yield(&self) -> &IndexMap<Species, Yield> {
+        &self.yields
+    }
+
+    pub fn cells(&self) -> &IndexMap<(usize, usize), Cell> {
+        &self.cells
+    }
+
+    // Coordinates of the cells with animals.
+    pub fn inhabited(&self) -> &[(usize, usize)] {
+        &self.inhabited
+    }
+
+    // Number of animals of each species on each island of the archipelago.
+    pub fn animals_per_island(&self) -> IndexMap<usize, IndexMap<Species, u32>> {
+        let mut islands: IndexMap<usize, IndexMap<Species, u32>> = (0..self.archipelago.count())
+            .map(|island| (island, Species::ALL.iter().map(|species| (*species, 0)).collect()))
+            .collect();
+
+        for coordinate in self.inhabited.iter() {
+            let Some(island) = self.archipelago.island(coordinate) else {
+                continue
+            };
+            let counts = islands.get_mut(&island).expect("Expected island");
+            for (species, animals) in self.cells[coordinate].animals.iter() {
+                *counts.get_mut(species).expect("Expected species") += animals.len() as u32;
+            }
+        }
+        islands
     }
 }
 
-struct Cell {
-    f_max: f32,
-    fodder: f32,
-    animals: IndexMap<Species, Vec<Animal>>,
+#[derive(Clone)]
+pub struct Cell {
+    pub terrain: u8,
+    pub vegetation: Vegetation,
+    // Biomass of dead animals and of the uneaten parts of prey.
+    pub carcass: f32,
+    // Species and age of the animals that died in the last aging phase, until they are counted
+    // by the island.
+    pub dead: Vec<(Species, u32)>,
+    pub animals: IndexMap<Species, Vec<Animal>>,
 }
 
 impl Cell {
-    const ALPHA: f32 = 0.1;
-    const V_MAX: f32 = 800.0;
-
-    fn grow_fodder(&mut self) {
-        if self.f_max == 0.0 || self.fodder == self.f_max {
-            return
-        }
-        let growth = Cell::V_MAX * (
-                1.0 - Cell::ALPHA
-                    * (self.f_max - self.fodder) / self.f_max
-        );
-        self.fodder = f32::min(self.f_max, self.fodder + growth);
+    // Share of the carcass that rots away each year.
+    const DECAY: f32 = 0.5;
+
+    pub fn grow_fodder(&mut self, rng: &mut StdRng) {
+        self.vegetation.grow(rng);
+    }
+
+    // Fodder of all plants.
+    pub fn fodder(&self) -> f32 {
+        self.vegetation.fodder()
+    }
+
+    // Rots away part of the carcass, of which the share `enrichment` becomes fodder.
+    pub fn decay(&mut self, enrichment: f32) {
+        let decayed = Cell::DECAY * self.carcass;
+        self.carcass -= decayed;
+        self.vegetation.enrich(enrichment * decayed);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.animals.values().all(|animals| animals.is_empty())
+    }
+
+    // Food available to `species` in this cell: the fodder, carcass and prey of its diet.
+    fn food(&self, species: &Species, web: &FoodWeb) -> f32 {
+        let parameters = species.parameters();
+        let mut food: f32 = web.prey(*species).iter()
+            .flat_map(|link| self.animals[&link.prey].iter())
+            .map(|animal| animal.weight)
+            .sum();
+        if parameters.grazing > 0.0 {
+            food += self.vegetation.pasture(species);
+        }
+        if parameters.scavenging > 0.0 {
+            food += self.carcass;
+        }
+        food
+    }
+
+    // Births become rarer as the cell fills up towards the carrying capacity in `density`.
+    fn procreate(&mut self, density: &IndexMap<Species, Density>, rng: &mut StdRng) -> Result<Counts> {
+        let mut counts = Counts::default();
+        for (species, animals) in self.animals.iter_mut() {
+            let parameters = species.parameters();
+            let (probability, procreation) = (
+                parameters.gamma * animals.len() as f32
+                    * density.get(species).map_or(1.0, |regulation| regulation.birth(self.terrain, animals.len())),
+                parameters.procreate,
+            );
+            let mut babies: Vec<Animal> = Vec::new();
+            for animal in animals.iter_mut() {
+                if animal.weight < procreation {
+                    continue
+                }
+                if rng.gen::<f32>() >= animal.fitness * probability {
+                    continue
+                }
+
+                let babyweight = birthweight(*species, rng)?;
+                if !animal.lose_weight_birth(babyweight) {
+                    continue
+                }
+
+                let mut baby = Animal {
+                    species: *species,
+                    age: 0,
+                    weight: babyweight,
+                    fitness: 0.0,
+                    health: Health::Susceptible,
+                };
+                baby.calculate_fitness();
+                babies.push(baby);
+            }
+            counts.births += babies.len() as u64;
+            animals.append(&mut babies);
+        }
+        Ok(counts)
+    }
+
+    // Species feed in the trophic order of `web`, so that predators eat of prey that has
+    // already eaten.
+    fn feed(&mut self, web: &FoodWeb, rng: &mut StdRng) -> Result<Counts> {
+        let mut counts = Counts::default();
+        self.grow_fodder(rng);
+        for species in web.order() {
+            if self.animals[species].is_empty() {
+                continue
+            }
+            if species.parameters().grazing >= 1.0 {
+                self.graze(species, &mut counts)?;
+            } else {
+                self.forage(species, web, rng, &mut counts)?;
+            }
+        }
+        Ok(counts)
+    }
+
+    // Animals that only graze eat in order of fitness, the fittest first.
+    fn graze(&mut self, species: &Species, counts: &mut Counts) -> Result<()> {
+        let animals = self.animals.get_mut(species).expect("Expected Species");
+        animals.sort_unstable_by_key(|animal| OrderedFloat(animal.fitness));
+
+        for animal in animals.iter_mut().rev() {
+            let eaten = animal.graze(self.vegetation.pasture(species))?;
+            self.vegetation.crop(species, eaten);
+            counts.grazing += 1;
+            if self.vegetation.pasture(species) == 0.0 {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    // Other animals eat in random order. Each grazes and scavenges its share of the hunger, and
+    // hunts its prey for the rest. Uneaten parts of the kills are left as carcass.
+    fn forage(&mut self, species: &Species, web: &FoodWeb, rng: &mut StdRng, counts: &mut Counts) -> Result<()> {
+        let parameters = species.parameters();
+        let links = web.prey(*species);
+        if parameters.grazing == 0.0 && parameters.scavenging == 0.0
+            && links.iter().all(|link| self.animals[&link.prey].is_empty()) {
+            return Ok(())
+        }
+
+        // The foragers are taken out of the cell while they eat, so that they can hunt the other
+        // species in place.
+        let mut animals = std::mem::take(self.animals.get_mut(species).expect("Expected Species"));
+        animals.shuffle(rng);
+        for animal in animals.iter_mut() {
+            let mut eaten = 0.0;
+            let pasture = self.vegetation.pasture(species);
+            if parameters.grazing > 0.0 && pasture > 0.0 {
+                let grazed = animal.graze(pasture)?;
+                self.vegetation.crop(species, grazed);
+                eaten += grazed;
+                counts.grazing += 1;
+            }
+            if parameters.scavenging > 0.0 && self.carcass > 0.0 {
+                let scavenged = animal.scavenge(self.carcass)?;
+                self.carcass -= scavenged;
+                eaten += scavenged;
+                counts.scavenging += 1;
+            }
+            for link in self.prey_order(&links, rng) {
+                if eaten >= parameters.hunger {
+                    break;
+                }
+                let prey = self.animals.get_mut(&link.prey).expect("Expected Species");
+                let hunt = animal.hunt(rng, prey, parameters.hunger - eaten, link.delta_phi_max)?;
+                counts.predation_attempts += hunt.attempts as u64;
+                counts.kills += hunt.kills as u64;
+                eaten += hunt.eaten;
+                self.carcass += hunt.leftovers;
+            }
+        }
+        *self.animals.get_mut(species).expect("Expected Species") = animals;
+        Ok(())
+    }
+
+    // The links to the prey that is present in the cell, in the order the prey is hunted.
+    // With more than one kind of prey, the next is drawn with probability proportional to its
+    // preference.
+    fn prey_order<'a>(&self, links: &[&'a Link], rng: &mut StdRng) -> Vec<&'a Link> {
+        let mut candidates: Vec<&Link> = links.iter()
+            .filter(|link| !self.animals[&link.prey].is_empty())
+            .copied()
+            .collect();
+        if candidates.len() < 2 {
+            return candidates
+        }
+
+        let mut order = Vec::with_capacity(candidates.len());
+        while !candidates.is_empty() {
+            let total: f32 = candidates.iter().map(|link| link.preference).sum();
+            let mut draw = rng.gen::<f32>() * total;
+            let next = candidates.iter()
+                .position(|link| {
+                    draw -= link.preference;
+                    draw < 0.0
+                })
+                .unwrap_or(candidates.len() - 1);
+            order.push(candidates.remove(next));
+        }
+        order
+    }
+
+    // Infects susceptible animals with a chance that grows with the number of infected animals
+    // in the cell. Infected animals lose weight, and may recover; recovered animals may become
+    // susceptible again.
+    fn spread(&mut self, disease: &Disease, rng: &mut StdRng) -> Counts {
+        let mut counts = Counts::default();
+        let infected = self.animals.values().flatten()
+            .filter(|animal| animal.health == Health::Infected)
+            .count() as u32;
+        if infected == 0 && disease.waning == 0.0 {
+            return counts
+        }
+
+        for (species, animals) in self.animals.iter_mut() {
+            let infection = disease.infection(species, infected);
+            for animal in animals.iter_mut() {
+                match animal.health {
+                    Health::Susceptible => if infection > 0.0 && rng.gen::<f32>() < infection {
+                        animal.health = Health::Infected;
+                        counts.infections += 1;
+                    },
+                    Health::Infected => {
+                        animal.weight -= disease.virulence * animal.weight;
+                        animal.calculate_fitness();
+                        if rng.gen::<f32>() < disease.recovery {
+                            animal.health = Health::Recovered;
+                            counts.recoveries += 1;
+                        }
+                    },
+                    Health::Recovered => if disease.waning > 0.0 && rng.gen::<f32>() < disease.waning {
+                        animal.health = Health::Susceptible;
+                    },
+                }
+            }
+        }
+        counts
+    }
+
+    // Animals that die are left as carcass, and their age is kept in `dead`.
+    fn aging(
+        &mut self,
+        layout: Layout,
+        mortality: &IndexMap<Species, Mortality>,
+        density: &IndexMap<Species, Density>,
+        rng: &mut StdRng,
+    ) -> Counts {
+        let mut counts = Counts::default();
+        let (mut carcass, mut dead) = (0.0, Vec::new());
+        self.animals.iter_mut()
+            .for_each(|(species, animals)| {
+                let before = animals.len();
+                let parameters = species.parameters();
+                let omega = parameters.omega;
+                let Mortality { hazard, max_age } = mortality.get(species).copied().unwrap_or_default();
+                let crowding = density.get(species).map_or(0.0, |regulation| regulation.death(self.terrain, before));
+
+                let mut dies = |animal: &Animal| {
+                    let dies = animal.weight <= 0.0f32
+                        ||
+                        max_age.is_some_and(|max_age| animal.age > max_age)
+                        ||
+                        rng.gen::<f32>() < omega * (1.0f32 - animal.fitness)
+                        ||
+                        (hazard != Hazard::None && rng.gen::<f32>() < hazard.probability(animal.age))
+                        ||
+                        (crowding > 0.0 && rng.gen::<f32>() < crowding);
+                    if dies {
+                        carcass += animal.weight.max(0.0);
+                        dead.push((*species, animal.age));
+                    }
+                    dies
+                };
+
+                if layout == Layout::Columns {
+                    Herd::scratch(|herd| {
+                        herd.gather(animals);
+                        herd.aging();
+                        herd.lose_weight_year(parameters);
+                        herd.calculate_fitness(parameters);
+                        herd.scatter(animals);
+                    });
+                    animals.retain(|animal| !dies(animal));
+                } else {
+                    animals.retain_mut(|animal| {
+                        animal.aging();
+                        animal.lose_weight_year();
+                        animal.calculate_fitness();
+                        !dies(animal)
+                    });
+                }
+                counts.deaths += (before - animals.len()) as u64;
+            });
+        self.carcass += carcass;
+        self.dead.append(&mut dead);
+        counts
     }
 }
\ No newline at end of file
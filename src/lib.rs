@@ -0,0 +1,23 @@
+pub mod animals;
+pub mod archipelago;
+pub mod disease;
+pub mod density;
+pub mod error;
+pub mod growth;
+pub mod harvest;
+pub mod foodweb;
+pub mod island;
+pub mod herd;
+pub mod invariants;
+pub mod migration;
+pub mod mortality;
+pub mod neighbourhood;
+pub mod streams;
+pub mod topology;
+pub mod vegetation;
+pub mod profile;
+pub mod graphics;
+pub mod simulation;
+pub mod validation;
+
+pub use error::{Error, Result};
@@ -1,11 +1,9 @@
-pub mod animals;
-pub mod island;
-mod graphics;
-// mod benchmark;
-mod simulation;
+use ecosystem_rust::{animals, simulation, Error};
 
-fn main() {
-    let mut rng = rand::thread_rng();
+fn main() -> Result<(), Error> {
+    let seed: u64 = rand::random();
+    // Print the time and events of each phase with `--profile`.
+    let profiling = std::env::args().skip(1).any(|argument| argument == "--profile");
 
     // let geography: Vec<&str> = vec![
     //     "WWWWW",
@@ -26,12 +24,17 @@ fn main() {
     ];
 
     let mut sim = simulation::Simulation::new(
-        geography, &mut rng, "graph_new.png"
-    );
+        geography, seed, "graph_new.png"
+    )?;
+    sim.island.profiling = profiling;
     sim.add_population(vec![
         ((2, 2), animals::Species::Herbivore, 100),
         ((2, 2), animals::Species::Carnivore, 10)
-    ]);
+    ])?;
 
-    sim.simulate(1000, true);
+    sim.simulate(1000, true)?;
+    if profiling {
+        println!("{}", sim.profile());
+    }
+    Ok(())
 }
@@ -0,0 +1,144 @@
+use std::sync::Arc;
+use indexmap::IndexMap;
+use ordered_float::OrderedFloat;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use super::animals::*;
+use super::topology::*;
+
+// Food and population of one species in every cell, at the start of the migration.
+#[derive(Default)]
+pub struct Landscape {
+    pub food: IndexMap<(usize, usize), f32>,
+    pub population: IndexMap<(usize, usize), u32>,
+}
+
+// What a migrating animal can see of the island around it.
+pub struct Surroundings<'a> {
+    pub origin: (usize, usize),
+    pub species: Species,
+    pub topology: &'a Topology,
+    pub geography: &'a [&'a [u8]],
+    pub landscape: &'a Landscape,
+}
+
+impl Surroundings<'_> {
+    fn parameters(&self) -> &'static Parameters {
+        self.species.parameters()
+    }
+
+    pub fn passable(&self, (i, j): (usize, usize)) -> bool {
+        i < self.geography.len() && j < self.geography[i].len() && self.geography[i][j] != b'W'
+    }
+
+    // Passable cells within `stride` of the origin, excluding the origin itself.
+    pub fn neighbours(&self) -> Vec<(usize, usize)> {
+        self.topology.within(self.origin, self.parameters().stride)
+            .into_iter()
+            .filter(|&cell| self.passable(cell))
+            .collect()
+    }
+
+    // Cells adjacent to the origin, passable or not.
+    pub fn adjacent(&self) -> Vec<(usize, usize)> {
+        self.topology.adjacent(self.origin)
+    }
+
+    // Food per individual (including the newcomer) in `cell`, relative to the appetite.
+    pub fn abundance(&self, cell: (usize, usize)) -> f32 {
+        let hunger = self.parameters().hunger as u128;
+        let fodder = self.landscape.food.get(&cell).copied().unwrap_or(0.0);
+        let population = self.landscape.population.get(&cell).copied().unwrap_or(0) as u128;
+        fodder
+            / (((population + 1) * hunger)
+            .max(population + 1)
+            .max(hunger)
+            .max(1)) as f32
+    }
+}
+
+// Rule for where a migrating animal moves to. `None` means that the animal stays.
+pub trait MigrationStrategy: Send + Sync {
+    fn destination(&self, surroundings: &Surroundings, rng: &mut StdRng) -> Option<(usize, usize)>;
+}
+
+// Original BioSim rule: one of the adjacent cells (four on a square grid) is chosen at random,
+// and the animal only moves if that cell is passable.
+pub struct BioSim;
+
+impl MigrationStrategy for BioSim {
+    fn destination(&self, surroundings: &Surroundings, rng: &mut StdRng) -> Option<(usize, usize)> {
+        let cell = *surroundings.adjacent().choose(rng)?;
+        if surroundings.passable(cell) { Some(cell) } else { None }
+    }
+}
+
+// A cell within stride is chosen at random, and accepted with probability proportional to its
+// share of the total abundance. With more than four cells, the abundances are sorted and only the
+// four lowest are kept, while the cell is still picked by its index among all the cells.
+pub struct Propensity;
+
+impl MigrationStrategy for Propensity {
+    fn destination(&self, surroundings: &Surroundings, rng: &mut StdRng) -> Option<(usize, usize)> {
+        let possibilities = surroundings.neighbours();
+        let mut propensities: Vec<f32> = possibilities.iter()
+            .map(|&cell| surroundings.abundance(cell))
+            .collect();
+
+        if propensities.is_empty() {
+            return None;
+        }
+
+        // Only consider the four best possibilities.
+        if propensities.len() > 4 {
+            propensities.sort_unstable_by_key(|&propensity| OrderedFloat(propensity));
+            propensities.drain(4..);
+        }
+        let propensity: f32 = propensities.iter().sum();
+
+        let chosen = rng.gen_range(0..propensities.len());
+
+        let probability = if propensity == 0.0f32 {
+            0.5
+        } else {
+            propensities[chosen] / propensity
+        };
+
+        if rng.gen::<f32>() < probability {
+            return Some(possibilities[chosen])
+        };
+        None
+    }
+}
+
+// Uniformly random passable cell within stride.
+pub struct RandomWalk;
+
+impl MigrationStrategy for RandomWalk {
+    fn destination(&self, surroundings: &Surroundings, rng: &mut StdRng) -> Option<(usize, usize)> {
+        surroundings.neighbours().choose(rng).copied()
+    }
+}
+
+// The most abundant cell within stride, if it is more abundant than the origin.
+pub struct Gradient;
+
+impl MigrationStrategy for Gradient {
+    fn destination(&self, surroundings: &Surroundings, _rng: &mut StdRng) -> Option<(usize, usize)> {
+        let here = surroundings.abundance(surroundings.origin);
+        surroundings.neighbours()
+            .into_iter()
+            .map(|cell| (cell, surroundings.abundance(cell)))
+            .filter(|&(_, abundance)| abundance > here)
+            .max_by_key(|&(_, abundance)| OrderedFloat(abundance))
+            .map(|(cell, _)| cell)
+    }
+}
+
+// Migration strategy of each species.
+pub fn default_strategies() -> IndexMap<Species, Arc<dyn MigrationStrategy>> {
+    Species::ALL.iter()
+        .map(|species| (*species, Arc::new(Propensity) as Arc<dyn MigrationStrategy>))
+        .collect()
+}
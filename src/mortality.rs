@@ -0,0 +1,88 @@
+use indexmap::IndexMap;
+use super::animals::*;
+use super::error::*;
+
+// Age-dependent hazard of dying, on top of the fitness-dependent death of every species.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Hazard {
+    None,
+    // Hazard a * exp(b * age), which grows exponentially with age.
+    Gompertz { a: f32, b: f32 },
+    // Hazard (shape / scale) * (age / scale)^(shape - 1).
+    Weibull { shape: f32, scale: f32 },
+}
+
+impl Hazard {
+    // Hazard accumulated from birth until `age`.
+    fn cumulative(&self, age: f32) -> f32 {
+        match *self {
+            Hazard::None => 0.0,
+            Hazard::Gompertz { a, b } => a / b * ((b * age).exp() - 1.0),
+            Hazard::Weibull { shape, scale } => (age / scale).powf(shape),
+        }
+    }
+
+    // Chance that an animal dies in the year before it reaches `age`.
+    pub fn probability(&self, age: u32) -> f32 {
+        let age = age as f32;
+        1.0 - (self.cumulative((age - 1.0).max(0.0)) - self.cumulative(age)).exp()
+    }
+}
+
+// Age-structured mortality of one species.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mortality {
+    pub hazard: Hazard,
+    // Animals older than this die.
+    pub max_age: Option<u32>,
+}
+
+impl Default for Mortality {
+    fn default() -> Mortality {
+        Mortality { hazard: Hazard::None, max_age: None }
+    }
+}
+
+impl Mortality {
+    pub fn validate(&self) -> Result<()> {
+        let valid = match self.hazard {
+            Hazard::None => true,
+            Hazard::Gompertz { a, b } => a >= 0.0 && b > 0.0,
+            Hazard::Weibull { shape, scale } => shape > 0.0 && scale > 0.0,
+        };
+        if !valid {
+            return Err(Error::InvalidParameter(format!("hazard {:?}", self.hazard)))
+        }
+        Ok(())
+    }
+}
+
+// Mortality of each species.
+pub fn default_mortality() -> IndexMap<Species, Mortality> {
+    Species::ALL.iter()
+        .map(|species| (*species, Mortality::default()))
+        .collect()
+}
+
+// Mean of a histogram where entry `i` is the number of animals that died at age `i`.
+pub fn mean_age(ages: &[u64]) -> Option<f64> {
+    let deaths: u64 = ages.iter().sum();
+    if deaths == 0 {
+        return None
+    }
+    let total: f64 = ages.iter().enumerate().map(|(age, n)| age as f64 * *n as f64).sum();
+    Some(total / deaths as f64)
+}
+
+// Smallest age at which at least the share `q` of the animals of the histogram had died.
+pub fn quantile(ages: &[u64], q: f64) -> Option<u32> {
+    let deaths: u64 = ages.iter().sum();
+    if deaths == 0 {
+        return None
+    }
+    let mut dead = 0;
+    ages.iter().position(|n| {
+        dead += n;
+        dead as f64 >= q * deaths as f64
+    }).map(|age| age as u32)
+}
@@ -0,0 +1,23 @@
+// Distance metric on the grid.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Metric {
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+}
+
+impl Metric {
+    // Whether `to` lies within `radius` of `from`.
+    pub fn within(&self, (x, y): (usize, usize), (i, j): (usize, usize), radius: usize) -> bool {
+        self.reaches(x.abs_diff(i), y.abs_diff(j), radius)
+    }
+
+    // Whether a cell `dx` rows and `dy` columns away lies within `radius`.
+    pub fn reaches(&self, dx: usize, dy: usize, radius: usize) -> bool {
+        match self {
+            Metric::Euclidean => dx * dx + dy * dy <= radius * radius,
+            Metric::Manhattan => dx + dy <= radius,
+            Metric::Chebyshev => dx.max(dy) <= radius,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Add;
+use std::time::Duration;
+use indexmap::IndexMap;
+use super::streams::Phase;
+
+// Number of events of each kind in (a part of) the yearly cycle.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct Counts {
+    pub births: u64,
+    pub grazing: u64,
+    pub scavenging: u64,
+    pub predation_attempts: u64,
+    pub kills: u64,
+    pub migration_attempts: u64,
+    pub migrations: u64,
+    pub deaths: u64,
+    pub infections: u64,
+    pub recoveries: u64,
+    pub harvested: u64,
+}
+
+impl Add for Counts {
+    type Output = Counts;
+
+    fn add(self, other: Counts) -> Counts {
+        Counts {
+            births: self.births + other.births,
+            grazing: self.grazing + other.grazing,
+            scavenging: self.scavenging + other.scavenging,
+            predation_attempts: self.predation_attempts + other.predation_attempts,
+            kills: self.kills + other.kills,
+            migration_attempts: self.migration_attempts + other.migration_attempts,
+            migrations: self.migrations + other.migrations,
+            deaths: self.deaths + other.deaths,
+            infections: self.infections + other.infections,
+            recoveries: self.recoveries + other.recoveries,
+            harvested: self.harvested + other.harvested,
+        }
+    }
+}
+
+// Wall time and event counts accumulated over the yearly cycles of an `Island`.
+#[derive(Default, Clone, Debug)]
+pub struct Profile {
+    pub years: u32,
+    pub time: IndexMap<Phase, Duration>,
+    pub counts: Counts,
+}
+
+impl Profile {
+    pub fn total(&self) -> Duration {
+        self.time.values().sum()
+    }
+}
+
+impl Display for Profile {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> std::fmt::Result {
+        let total = self.total().as_secs_f64().max(f64::MIN_POSITIVE);
+        writeln!(f, "Profile over {} years:", self.years)?;
+        for (phase, time) in self.time.iter() {
+            writeln!(f, "  {:<10} {:>12.3?} {:>6.1}%",
+                     phase.to_string(), time, 100.0 * time.as_secs_f64() / total)?;
+        }
+        let counts = &self.counts;
+        writeln!(f, "  births              {}", counts.births)?;
+        writeln!(f, "  grazing             {}", counts.grazing)?;
+        writeln!(f, "  scavenging          {}", counts.scavenging)?;
+        writeln!(f, "  predation attempts  {} ({} kills)", counts.predation_attempts, counts.kills)?;
+        writeln!(f, "  migration attempts  {} ({} moved)", counts.migration_attempts, counts.migrations)?;
+        writeln!(f, "  deaths              {}", counts.deaths)?;
+        writeln!(f, "  infections          {} ({} recovered)", counts.infections, counts.recoveries)?;
+        write!(f, "  harvested           {}", counts.harvested)
+    }
+}
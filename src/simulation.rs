@@ -1,8 +1,12 @@
 use indexmap::IndexMap;
-use rand::prelude::ThreadRng;
 use super::animals::*;
+use super::disease::*;
+use super::error::*;
 use super::island::*;
 use super::graphics::*;
+use super::harvest::*;
+use super::profile::*;
+use super::topology::*;
 
 pub struct Simulation<'a> {
     pub island: Island<'a>,
@@ -10,78 +14,142 @@ pub struct Simulation<'a> {
 
     animals: IndexMap<Species, Vec<u32>>,
     placement: IndexMap<(usize, usize), IndexMap<Species, Vec<u32>>>,
+    islands: IndexMap<usize, IndexMap<Species, Vec<u32>>>,
+    epidemic: Vec<Epidemic>,
+    yields: Vec<IndexMap<Species, Yield>>,
 }
 
 impl Simulation<'_> {
-    pub fn new<'a>(geography: Vec<&'a str>, rng: &'a mut ThreadRng, path: &'static str) ->
-                                                                                       Simulation<'a> {
-        let isl = Island::new(geography, rng);
+    pub fn new<'a>(geography: Vec<&'a str>, seed: u64, path: &'static str) -> Result<Simulation<'a>> {
+        Simulation::with_topology(geography, seed, Grid::Square4, false, path)
+    }
+
+    pub fn with_topology<'a>(
+        geography: Vec<&'a str>, seed: u64, grid: Grid, toroidal: bool, path: &'static str
+    ) -> Result<Simulation<'a>> {
+        let isl = Island::with_topology(geography, seed, grid, toroidal)?;
         let mut animals = IndexMap::new();
         let mut placement = IndexMap::new();
 
-        for species in [Species::Herbivore, Species::Carnivore].iter() {
+        for species in Species::ALL.iter() {
             animals.insert(*species, Vec::new());
         }
 
         for x in 0..isl.geography.len() {
             for y in 0..isl.geography[0].len() {
                 let mut species = IndexMap::new();
-                for _species in [Species::Herbivore, Species::Carnivore].iter() {
+                for _species in Species::ALL.iter() {
                     species.insert(*_species, Vec::new());
                 }
                 placement.insert((x, y), species);
             }
         }
 
-        Simulation {
+        let islands = (0..isl.archipelago.count())
+            .map(|island| {
+                (island, Species::ALL.iter()
+                    .map(|species| (*species, Vec::new()))
+                    .collect())
+            })
+            .collect();
+
+        Ok(Simulation {
             island: isl,
             graphics: Graphics { path },
             animals,
             placement,
-        }
+            islands,
+            epidemic: Vec::new(),
+            yields: Vec::new(),
+        })
     }
 
-    pub fn add_population(&mut self, population: Vec<((usize, usize), Species, u16)>) {
-        self.island.add_population(population);
+    pub fn add_population(&mut self, population: Vec<((usize, usize), Species, u16)>) -> Result<()> {
+        self.island.add_population(population)
     }
 
-    pub fn simulate(&mut self, years: u16, graph: bool) {
+    pub fn simulate(&mut self, years: u16, graph: bool) -> Result<()> {
 
         self.metrics();
 
         for _ in 0..years {
-            self.island.yearly_cycle();
+            self.island.yearly_cycle()?;
             self.metrics();
         }
 
-        if graph { self.graph(); }
+        if graph { self.graph()?; }
+        Ok(())
     }
 
     fn metrics(&mut self) {
         let (n_species, n_cell) = self.island.animals();
         n_species.iter().for_each(|(species, n)| {
             self.animals
-                .get_mut(species).expect("Species error.")
+                .entry(*species).or_default()
                 .push(*n);
         });
         n_cell.iter().for_each(|((x, y), species)| {
             species.iter().for_each(|(species, n)| {
                 self.placement
-                    .get_mut(&(*x, *y)).expect("Placement error.")
-                    .get_mut(species).expect("Species error.")
+                    .entry((*x, *y)).or_default()
+                    .entry(*species).or_default()
+                    .push(*n);
+            });
+        });
+        self.island.animals_per_island().iter().for_each(|(island, species)| {
+            species.iter().for_each(|(species, n)| {
+                self.islands
+                    .entry(*island).or_default()
+                    .entry(*species).or_default()
                     .push(*n);
             });
         });
+        self.epidemic.push(self.island.epidemic());
+        self.yields.push(self.island.harvest_yield().clone());
+    }
+
+    // Yearly number of animals of each species on the whole map.
+    pub fn population(&self) -> &IndexMap<Species, Vec<u32>> {
+        &self.animals
+    }
+
+    // Yearly number of animals of each species on each island of the archipelago.
+    pub fn islands(&self) -> &IndexMap<usize, IndexMap<Species, Vec<u32>>> {
+        &self.islands
+    }
+
+    // Yearly state of the disease, when `island.disease` is set.
+    pub fn epidemic(&self) -> &[Epidemic] {
+        &self.epidemic
+    }
+
+    // Yearly yield of each harvested species, when `island.harvesting` is set.
+    pub fn yields(&self) -> &[IndexMap<Species, Yield>] {
+        &self.yields
+    }
+
+    // Per-phase wall time and event counts, when `island.profiling` is enabled.
+    pub fn profile(&self) -> &Profile {
+        &self.island.profile
     }
 
     pub fn reset(&mut self) {
-        self.animals.clear();
-        self.placement.iter_mut().for_each(|(_, species)| {
-            species.clear();
+        self.island.profile = Profile::default();
+        self.animals.values_mut().for_each(Vec::clear);
+        self.epidemic.clear();
+        self.yields.clear();
+        self.placement.values_mut().chain(self.islands.values_mut()).for_each(|species| {
+            species.values_mut().for_each(Vec::clear);
         });
     }
 
-    pub fn graph(&self) {
-        self.graphics.graph(&self.animals);
+    pub fn graph(&self) -> Result<()> {
+        self.graphics.graph(&self.animals)
+    }
+
+    // Draws the island and where the animals currently are.
+    pub fn map(&self, path: &str) -> Result<()> {
+        let (_, placement) = self.island.animals();
+        self.graphics.map(path, &self.island.topology, &self.island.geography, &placement)
     }
 }
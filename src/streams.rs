@@ -0,0 +1,50 @@
+use std::fmt::{Display, Formatter};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+// Phases of the yearly cycle that draw random numbers per cell.
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub enum Phase {
+    Procreate,
+    Feed,
+    Migrate,
+    Aging,
+    Disease,
+    Harvest,
+}
+
+impl Display for Phase {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> std::fmt::Result {
+        match self {
+            Phase::Procreate => write!(f, "procreate"),
+            Phase::Feed => write!(f, "feed"),
+            Phase::Migrate => write!(f, "migrate"),
+            Phase::Aging => write!(f, "aging"),
+            Phase::Disease => write!(f, "disease"),
+            Phase::Harvest => write!(f, "harvest"),
+        }
+    }
+}
+
+// Independent random number stream for one cell in one phase of one year.
+//
+// The stream only depends on (seed, year, cell, phase), and not on the order in which cells are
+// visited, so a seeded run gives the same result regardless of the number of threads.
+//
+// The seed is scrambled before the other fields are mixed in, so that runs with consecutive seeds
+// do not share streams (with `seed ^ year`, seed 1 in year 0 would repeat seed 0 in year 1).
+pub fn stream(seed: u64, year: u16, (x, y): (usize, usize), phase: Phase) -> StdRng {
+    let mut state = splitmix(seed);
+    for value in [year as u64, x as u64, y as u64, phase as u64] {
+        state = splitmix(state ^ value);
+    }
+    StdRng::seed_from_u64(state)
+}
+
+// SplitMix64 finaliser, used to scramble the stream key into a seed.
+fn splitmix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,187 @@
+use super::error::*;
+use super::neighbourhood::*;
+
+// Shape of the cells of the map.
+//
+// Square grids have four (`Square4`) or eight (`Square8`) adjacent cells.
+// Hexagonal grids use the "odd-r" offset-row layout: every odd row is shifted half a cell to the
+// right, and each cell has six adjacent cells.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Grid {
+    Square4,
+    Square8,
+    Hexagonal,
+}
+
+// How the cells of a map are connected.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct Topology {
+    pub grid: Grid,
+
+    // Distance used for the cells within stride on square grids.
+    // Hexagonal grids always count the number of steps between cells.
+    pub metric: Metric,
+
+    // Opposite edges of the map are joined, so that there is no border.
+    pub toroidal: bool,
+
+    pub rows: usize,
+    pub columns: usize,
+}
+
+impl Topology {
+    pub fn new(grid: Grid, toroidal: bool, (rows, columns): (usize, usize)) -> Result<Topology> {
+        if grid == Grid::Hexagonal && toroidal && rows % 2 != 0 {
+            return Err(Error::InvalidGeography(
+                "toroidal hexagonal maps need an even number of rows".to_string()
+            ))
+        }
+        Ok(Topology {
+            grid,
+            metric: Metric::Euclidean,
+            toroidal,
+            rows, columns,
+        })
+    }
+
+    // Cells sharing an edge (or, for `Square8`, a corner) with `cell`.
+    pub fn adjacent(&self, cell: (usize, usize)) -> Vec<(usize, usize)> {
+        match self.grid {
+            Grid::Square4 => self.around(cell, 1, |dx, dy| Metric::Manhattan.reaches(dx, dy, 1)),
+            Grid::Square8 => self.around(cell, 1, |dx, dy| Metric::Chebyshev.reaches(dx, dy, 1)),
+            Grid::Hexagonal => self.within(cell, 1),
+        }
+    }
+
+    // Cells within `radius` of `origin`, excluding the origin itself.
+    pub fn within(&self, origin: (usize, usize), radius: usize) -> Vec<(usize, usize)> {
+        match self.grid {
+            Grid::Hexagonal => {
+                let mut cells = self.candidates(origin, radius);
+                cells.retain(|&cell| self.hexagonal_distance(origin, cell) <= radius);
+                cells
+            }
+            _ => self.around(origin, radius, |dx, dy| self.metric.reaches(dx, dy, radius)),
+        }
+    }
+
+    // Cells within the square of `radius` around `origin` that satisfy `reaches` for their
+    // (absolute) row and column distance.
+    fn around<F>(&self, origin: (usize, usize), radius: usize, reaches: F) -> Vec<(usize, usize)>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let mut cells = self.candidates(origin, radius);
+        cells.retain(|&cell| {
+            let (dx, dy) = self.offset(origin, cell);
+            reaches(dx.unsigned_abs(), dy.unsigned_abs())
+        });
+        cells
+    }
+
+    // Every cell in the square of `radius` around `origin`, wrapped or clipped to the map.
+    fn candidates(&self, (x, y): (usize, usize), radius: usize) -> Vec<(usize, usize)> {
+        let radius = radius as isize;
+        let mut cells: Vec<(usize, usize)> = (-radius..=radius)
+            .flat_map(|di| (-radius..=radius).map(move |dj| (di, dj)))
+            .filter_map(|(di, dj)| self.step((x, y), di, dj))
+            .filter(|&cell| cell != (x, y))
+            .collect();
+        cells.sort_unstable();
+        cells.dedup();
+        cells
+    }
+
+    fn step(&self, (x, y): (usize, usize), di: isize, dj: isize) -> Option<(usize, usize)> {
+        let (i, j) = (x as isize + di, y as isize + dj);
+        if self.toroidal {
+            return Some((
+                i.rem_euclid(self.rows as isize) as usize,
+                j.rem_euclid(self.columns as isize) as usize,
+            ))
+        }
+        if i < 0 || j < 0 || i >= self.rows as isize || j >= self.columns as isize {
+            return None
+        }
+        Some((i as usize, j as usize))
+    }
+
+    // Row and column steps from `from` to `to`, the short way around on a torus.
+    fn offset(&self, (x, y): (usize, usize), (i, j): (usize, usize)) -> (isize, isize) {
+        let (mut dx, mut dy) = (i as isize - x as isize, j as isize - y as isize);
+        if self.toroidal {
+            let (rows, columns) = (self.rows as isize, self.columns as isize);
+            if 2 * dx.abs() > rows {
+                dx -= rows * dx.signum();
+            }
+            if 2 * dy.abs() > columns {
+                dy -= columns * dy.signum();
+            }
+        }
+        (dx, dy)
+    }
+
+    fn hexagonal_distance(&self, from: (usize, usize), to: (usize, usize)) -> usize {
+        let images: Vec<(isize, isize)> = if self.toroidal {
+            let (rows, columns) = (self.rows as isize, self.columns as isize);
+            [-rows, 0, rows].iter()
+                .flat_map(|&di| [-columns, 0, columns].map(move |dj| (di, dj)))
+                .collect()
+        } else {
+            vec![(0, 0)]
+        };
+        let (q, r) = axial((from.0 as isize, from.1 as isize));
+        images.iter()
+            .map(|(di, dj)| {
+                let (q_to, r_to) = axial((to.0 as isize + di, to.1 as isize + dj));
+                let (dq, dr) = (q_to - q, r_to - r);
+                ((dq.abs() + dr.abs() + (dq + dr).abs()) / 2) as usize
+            })
+            .min()
+            .unwrap_or(0)
+    }
+
+    // Centre of `cell` in drawing coordinates, where the cells are one unit wide.
+    pub fn centre(&self, (i, j): (usize, usize)) -> (f64, f64) {
+        match self.grid {
+            Grid::Hexagonal => (
+                j as f64 + 0.5 + 0.5 * (i % 2) as f64,
+                1.0 / 3.0f64.sqrt() + i as f64 * 0.75f64.sqrt(),
+            ),
+            _ => (j as f64 + 0.5, i as f64 + 0.5),
+        }
+    }
+
+    // Corners of `cell` in drawing coordinates.
+    pub fn outline(&self, cell: (usize, usize)) -> Vec<(f64, f64)> {
+        let (x, y) = self.centre(cell);
+        match self.grid {
+            Grid::Hexagonal => {
+                let size = 1.0 / 3.0f64.sqrt();
+                (0..6)
+                    .map(|k| {
+                        let angle = (30.0 + 60.0 * k as f64).to_radians();
+                        (x + size * angle.cos(), y + size * angle.sin())
+                    })
+                    .collect()
+            }
+            _ => vec![(x - 0.5, y - 0.5), (x + 0.5, y - 0.5), (x + 0.5, y + 0.5), (x - 0.5, y + 0.5)],
+        }
+    }
+
+    // Width and height of the map in drawing coordinates.
+    pub fn extent(&self) -> (f64, f64) {
+        match self.grid {
+            Grid::Hexagonal => (
+                self.columns as f64 + 0.5,
+                (self.rows.max(1) - 1) as f64 * 0.75f64.sqrt() + 2.0 / 3.0f64.sqrt(),
+            ),
+            _ => (self.columns as f64, self.rows as f64),
+        }
+    }
+}
+
+// Axial coordinates of an "odd-r" offset cell.
+fn axial((row, column): (isize, isize)) -> (isize, isize) {
+    (column - (row - row.rem_euclid(2)) / 2, row)
+}
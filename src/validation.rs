@@ -0,0 +1,368 @@
+use std::fmt::{Display, Formatter};
+use rayon::prelude::*;
+use super::animals::*;
+use super::error::*;
+use super::simulation::*;
+
+// Setup that is run for every replicate. The Python reference must be exported from the same map,
+// initial population and number of years.
+pub struct Configuration<'a> {
+    pub geography: Vec<&'a str>,
+    pub population: Vec<((usize, usize), Species, u16)>,
+    pub years: u16,
+}
+
+// Yearly population of one run, from the initial population (year 0) onwards.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replicate {
+    pub herbivores: Vec<u32>,
+    pub carnivores: Vec<u32>,
+}
+
+impl Replicate {
+    // The Python implementation only has herbivores and carnivores.
+    pub fn series(&self, species: Species) -> &[u32] {
+        match species {
+            Species::Herbivore => &self.herbivores,
+            Species::Carnivore => &self.carnivores,
+            Species::Omnivore | Species::Scavenger | Species::Apex => &[],
+        }
+    }
+
+    // Year of the first carnivore peak: the first local maximum that reaches at least half of the
+    // largest number of carnivores, so that small fluctuations early on are not counted.
+    pub fn first_peak(&self) -> Option<usize> {
+        let series = &self.carnivores;
+        let highest = *series.iter().max()?;
+        if highest == 0 {
+            return None
+        }
+        (0..series.len()).find(|&year| {
+            2 * series[year] >= highest
+                && (year == 0 || series[year] >= series[year - 1])
+                && (year + 1 == series.len() || series[year] > series[year + 1])
+        })
+    }
+
+    // Whether `species` has died out by the last year.
+    pub fn extinct(&self, species: Species) -> bool {
+        self.series(species).last().is_some_and(|&n| n == 0)
+    }
+}
+
+// Runs `replicates` seeded simulations of `configuration`, with seeds `seed`, `seed + 1`, ...,
+// wrapping around after `u64::MAX`.
+pub fn replicates(configuration: &Configuration, replicates: u64, seed: u64) -> Result<Vec<Replicate>> {
+    (0..replicates)
+        .into_par_iter()
+        .map(|replicate| {
+            let mut sim = Simulation::new(
+                configuration.geography.clone(), seed.wrapping_add(replicate), "validation.png"
+            )?;
+            sim.add_population(configuration.population.clone())?;
+            sim.simulate(configuration.years, false)?;
+            let population = sim.population();
+            Ok(Replicate {
+                herbivores: population[&Species::Herbivore].clone(),
+                carnivores: population[&Species::Carnivore].clone(),
+            })
+        })
+        .collect()
+}
+
+// Reads reference data exported from the Python implementation.
+//
+// One line per replicate and year, with a header:
+//
+//     replicate,year,herbivores,carnivores
+//     0,0,100,10
+//     0,1,132,10
+//     ...
+//
+// Years of each replicate must be consecutive from 0.
+pub fn read_reference(text: &str) -> Result<Vec<Replicate>> {
+    let header: Vec<&str> = text.lines().next().unwrap_or("").split(',').map(str::trim).collect();
+    if header != ["replicate", "year", "herbivores", "carnivores"] {
+        return Err(Error::InvalidData(
+            "line 1: expected the header replicate,year,herbivores,carnivores".to_string()
+        ))
+    }
+    let mut replicates: Vec<(u64, Replicate)> = Vec::new();
+    for (number, line) in text.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue
+        }
+        let malformed = |reason: &str| Error::InvalidData(format!("line {}: {}", number + 1, reason));
+        let fields: Vec<u64> = line
+            .split(',')
+            .map(|field| field.trim().parse().map_err(|_| malformed("expected whole numbers")))
+            .collect::<Result<_>>()?;
+        let [replicate, year, herbivores, carnivores] = fields[..] else {
+            return Err(malformed("expected four columns"))
+        };
+        if replicates.last().map(|(id, _)| *id) != Some(replicate) {
+            replicates.push((replicate, Replicate { herbivores: Vec::new(), carnivores: Vec::new() }));
+        }
+        let (_, current) = replicates.last_mut().expect("Expected replicate");
+        if year as usize != current.herbivores.len() {
+            return Err(malformed("years must be consecutive from 0"))
+        }
+        current.herbivores.push(herbivores as u32);
+        current.carnivores.push(carnivores as u32);
+    }
+    Ok(replicates.into_iter().map(|(_, replicate)| replicate).collect())
+}
+
+pub fn load_reference(path: &str) -> Result<Vec<Replicate>> {
+    read_reference(&std::fs::read_to_string(path)?)
+}
+
+// Reads the map and the initial population that the reference was run with:
+//
+//     map
+//     WWWWW
+//     WLLHW
+//     WWWWW
+//     population
+//     1,1,Herbivore,100
+//     1,1,Carnivore,10
+//
+// with one line per cell, species and number of animals. The number of years is left at 0, to be
+// set from the reference.
+pub fn read_configuration(text: &str) -> Result<Configuration<'_>> {
+    let mut configuration = Configuration { geography: Vec::new(), population: Vec::new(), years: 0 };
+    let mut section = None;
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue
+        }
+        let malformed = |reason: &str| Error::InvalidData(format!("line {}: {}", number + 1, reason));
+        match (line, section) {
+            ("map", _) | ("population", _) => section = Some(line),
+            (_, Some("map")) => configuration.geography.push(line),
+            (_, Some("population")) => {
+                let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+                let [i, j, species, amount] = fields[..] else {
+                    return Err(malformed("expected row,column,species,amount"))
+                };
+                let species = *Species::ALL.iter()
+                    .find(|candidate| candidate.to_string() == species)
+                    .ok_or_else(|| malformed(&format!("unknown species {}", species)))?;
+                let (Ok(i), Ok(j), Ok(amount)) = (i.parse(), j.parse(), amount.parse()) else {
+                    return Err(malformed("expected whole numbers"))
+                };
+                configuration.population.push(((i, j), species, amount));
+            },
+            _ => return Err(malformed("expected a map or population section")),
+        }
+    }
+    if configuration.geography.is_empty() {
+        return Err(Error::InvalidData("the configuration has no map".to_string()))
+    }
+    Ok(configuration)
+}
+
+// Outcome of one comparison between the two sets of replicates.
+#[derive(Clone, Debug)]
+pub struct Check {
+    pub name: String,
+    pub ours: f64,
+    pub reference: f64,
+    pub statistic: f64,
+    pub p: f64,
+}
+
+// Agreement between the replicates of this implementation and the reference.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub alpha: f64,
+    pub replicates: (usize, usize),
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    // Whether no check rejects equality at level `alpha`.
+    pub fn agrees(&self) -> bool {
+        self.checks.iter().all(|check| check.p >= self.alpha)
+    }
+}
+
+impl Display for Report {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> std::fmt::Result {
+        writeln!(f, "Validation against reference ({} vs. {} replicates, alpha = {}):",
+                 self.replicates.0, self.replicates.1, self.alpha)?;
+        writeln!(f, "  {:<34} {:>10} {:>10} {:>9} {:>8}", "check", "ours", "reference", "statistic", "p")?;
+        for check in self.checks.iter() {
+            writeln!(f, "  {:<34} {:>10.2} {:>10.2} {:>9.3} {:>8.4} {}",
+                     check.name, check.ours, check.reference, check.statistic, check.p,
+                     if check.p >= self.alpha { "ok" } else { "DIFFERS" })?;
+        }
+        let agreeing = self.checks.iter().filter(|check| check.p >= self.alpha).count();
+        write!(f, "  {}/{} checks agree: {}", agreeing, self.checks.len(),
+               if self.agrees() { "AGREEMENT" } else { "DISAGREEMENT" })
+    }
+}
+
+// Compares `ours` with `reference`:
+//   * mean population of each species per year (Welch's t-test; the smallest p-value over the
+//     years is Bonferroni corrected),
+//   * mean population of each species over all years (Welch's t-test),
+//   * year of the first carnivore peak (two-sample Kolmogorov-Smirnov test),
+//   * extinction rate of each species (two-proportion z-test).
+pub fn compare(ours: &[Replicate], reference: &[Replicate], alpha: f64) -> Report {
+    let mut checks = Vec::new();
+    for species in [Species::Herbivore, Species::Carnivore] {
+        let years = ours.iter().chain(reference)
+            .map(|replicate| replicate.series(species).len())
+            .min()
+            .unwrap_or(0);
+
+        let yearly: Vec<Check> = (0..years)
+            .map(|year| {
+                let at = |replicates: &[Replicate]| -> Vec<f64> {
+                    replicates.iter().map(|r| r.series(species)[year] as f64).collect()
+                };
+                let (a, b) = (at(ours), at(reference));
+                let (statistic, p) = welch(&a, &b);
+                Check {
+                    name: format!("{} population, year {}", species, year),
+                    ours: mean(&a), reference: mean(&b),
+                    statistic, p,
+                }
+            })
+            .collect();
+        if let Some(mut worst) = yearly.into_iter()
+            .min_by(|a, b| a.p.total_cmp(&b.p)) {
+            worst.p = (worst.p * years as f64).min(1.0);
+            checks.push(worst);
+        }
+
+        let average = |replicates: &[Replicate]| -> Vec<f64> {
+            replicates.iter()
+                .map(|r| mean(&r.series(species)[..years].iter().map(|&n| n as f64).collect::<Vec<f64>>()))
+                .collect()
+        };
+        let (a, b) = (average(ours), average(reference));
+        let (statistic, p) = welch(&a, &b);
+        checks.push(Check {
+            name: format!("{} mean population", species),
+            ours: mean(&a), reference: mean(&b),
+            statistic, p,
+        });
+    }
+
+    let peaks = |replicates: &[Replicate]| -> Vec<f64> {
+        replicates.iter().filter_map(|r| r.first_peak()).map(|year| year as f64).collect()
+    };
+    let (a, b) = (peaks(ours), peaks(reference));
+    if !a.is_empty() && !b.is_empty() {
+        let (statistic, p) = kolmogorov_smirnov(&a, &b);
+        checks.push(Check {
+            name: "Year of first Carnivore peak".to_string(),
+            ours: mean(&a), reference: mean(&b),
+            statistic, p,
+        });
+    }
+
+    for species in [Species::Herbivore, Species::Carnivore] {
+        let extinct = |replicates: &[Replicate]| {
+            replicates.iter().filter(|r| r.extinct(species)).count()
+        };
+        let (statistic, p) = proportions(
+            (extinct(ours), ours.len()), (extinct(reference), reference.len())
+        );
+        checks.push(Check {
+            name: format!("{} extinction rate", species),
+            ours: extinct(ours) as f64 / ours.len().max(1) as f64,
+            reference: extinct(reference) as f64 / reference.len().max(1) as f64,
+            statistic, p,
+        });
+    }
+
+    Report { alpha, replicates: (ours.len(), reference.len()), checks }
+}
+
+pub fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len().max(1) as f64
+}
+
+pub fn variance(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0
+    }
+    let mean = mean(values);
+    values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+// Welch's t statistic and its two-sided p-value. The p-value uses the normal approximation of
+// the t distribution, which is close for the 30 or more replicates that a validation needs.
+pub fn welch(a: &[f64], b: &[f64]) -> (f64, f64) {
+    let difference = mean(a) - mean(b);
+    let error = (variance(a) / a.len().max(1) as f64 + variance(b) / b.len().max(1) as f64).sqrt();
+    if error == 0.0 {
+        return if difference == 0.0 { (0.0, 1.0) } else { (f64::INFINITY, 0.0) }
+    }
+    let t = difference / error;
+    (t, 2.0 * (1.0 - normal_cdf(t.abs())))
+}
+
+// Two-sample Kolmogorov-Smirnov statistic and its asymptotic p-value.
+pub fn kolmogorov_smirnov(a: &[f64], b: &[f64]) -> (f64, f64) {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort_by(f64::total_cmp);
+    b.sort_by(f64::total_cmp);
+
+    let (mut i, mut j, mut d) = (0, 0, 0.0f64);
+    while i < a.len() && j < b.len() {
+        let x = a[i].min(b[j]);
+        while i < a.len() && a[i] <= x { i += 1; }
+        while j < b.len() && b[j] <= x { j += 1; }
+        d = d.max((i as f64 / a.len() as f64 - j as f64 / b.len() as f64).abs());
+    }
+
+    let n = (a.len() * b.len()) as f64 / (a.len() + b.len()) as f64;
+    let lambda = (n.sqrt() + 0.12 + 0.11 / n.sqrt()) * d;
+    // The series does not converge for small lambda, where the p-value is 1 to five decimals
+    // (and the terms add up to 0 for identical samples).
+    if lambda < 0.3 {
+        return (d, 1.0)
+    }
+    let mut p = 0.0;
+    for k in 1..=100 {
+        let k = k as f64;
+        let term = 2.0 * (-1.0f64).powf(k - 1.0) * (-2.0 * k * k * lambda * lambda).exp();
+        p += term;
+        if term.abs() <= 1e-10 * p.abs() {
+            return (d, p.clamp(0.0, 1.0))
+        }
+    }
+    (d, 1.0)
+}
+
+// Two-proportion z statistic for `successes` out of `trials`, and its two-sided p-value.
+pub fn proportions((x1, n1): (usize, usize), (x2, n2): (usize, usize)) -> (f64, f64) {
+    let (n1, n2) = (n1.max(1) as f64, n2.max(1) as f64);
+    let (p1, p2) = (x1 as f64 / n1, x2 as f64 / n2);
+    let pooled = (x1 + x2) as f64 / (n1 + n2);
+    let error = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+    if error == 0.0 {
+        return (0.0, 1.0)
+    }
+    let z = (p1 - p2) / error;
+    (z, 2.0 * (1.0 - normal_cdf(z.abs())))
+}
+
+pub fn normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / 2.0f64.sqrt()))
+}
+
+// Error function, Abramowitz and Stegun 7.1.26 (absolute error below 1.5e-7).
+pub fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let polynomial = t * (0.254829592
+        + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let y = 1.0 - polynomial * (-x * x).exp();
+    y.copysign(x)
+}
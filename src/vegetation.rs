@@ -0,0 +1,232 @@
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use indexmap::IndexMap;
+use rand::rngs::StdRng;
+use super::animals::*;
+use super::error::*;
+use super::growth::*;
+
+pub struct PlantParameters {
+    // Fodder capacity taken up by one unit of fodder of each plant in `Plant::ALL`, such as
+    // shrubs shading out grass.
+    pub competition: [f32; Plant::ALL.len()],
+}
+
+impl PlantParameters {
+    pub const GRASS: PlantParameters = PlantParameters {
+        competition: [0.0, 0.5],
+    };
+    pub const SHRUB: PlantParameters = PlantParameters {
+        competition: [0.1, 0.0],
+    };
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum Plant {
+    Grass,
+    Shrub,
+}
+
+impl Plant {
+    pub const ALL: [Plant; 2] = [Plant::Grass, Plant::Shrub];
+
+    pub fn parameters(&self) -> &'static PlantParameters {
+        match self {
+            Plant::Grass => &PlantParameters::GRASS,
+            Plant::Shrub => &PlantParameters::SHRUB,
+        }
+    }
+
+    // Food that `species` gets from one unit of fodder of this plant. Grazers eat the most
+    // palatable plants first, and can't eat plants with no palatability.
+    pub fn palatability(&self, species: &Species) -> f32 {
+        match (self, species) {
+            (Plant::Grass, Species::Herbivore | Species::Omnivore) => 1.0,
+            (Plant::Shrub, Species::Herbivore) => 0.3,
+            (Plant::Shrub, Species::Omnivore) => 0.6,
+            _ => 0.0,
+        }
+    }
+}
+
+impl Display for Plant {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> std::fmt::Result {
+        match self {
+            Plant::Grass => write!(f, "Grass"),
+            Plant::Shrub => write!(f, "Shrub"),
+        }
+    }
+}
+
+// Fodder of one plant in a cell.
+#[derive(Clone)]
+pub struct Stand {
+    pub f_max: f32,
+    pub fodder: f32,
+    pub growth: Arc<dyn GrowthModel>,
+    // Most fodder of the land before it was degraded by overgrazing.
+    pub potential: f32,
+    // Consecutive years that the plant has been overgrazed, or rested.
+    pub overgrazed: u32,
+    pub rested: u32,
+}
+
+impl Stand {
+    pub fn new(plant: Plant, f_max: f32) -> Stand {
+        Stand {
+            f_max,
+            fodder: f_max,
+            growth: default_growth(plant),
+            potential: f_max,
+            overgrazed: 0,
+            rested: 0,
+        }
+    }
+}
+
+// Seeds spread from the adjacent cells, so that fodder grows back by the share `rate` of the
+// mean fodder of each plant in the adjacent land cells.
+#[derive(Clone, Debug)]
+pub struct Dispersal {
+    pub rate: f32,
+}
+
+// Land that is grazed below the share `threshold` of its most fodder for `years` years in a row
+// loses the share `loss` of its most fodder every further year. After `rest` years without
+// overgrazing, it regains the share `recovery` of its potential every year.
+#[derive(Clone, Debug)]
+pub struct Degradation {
+    pub threshold: f32,
+    pub years: u32,
+    pub loss: f32,
+    pub rest: u32,
+    pub recovery: f32,
+}
+
+impl Degradation {
+    pub fn validate(&self) -> Result<()> {
+        let shares = [
+            ("threshold", self.threshold),
+            ("loss", self.loss),
+            ("recovery", self.recovery),
+        ];
+        if let Some((name, value)) = shares.iter().find(|(_, value)| !(0.0..=1.0).contains(value)) {
+            return Err(Error::InvalidParameter(format!("degradation {} {} is not in [0, 1]", name, value)))
+        }
+        Ok(())
+    }
+}
+
+impl Default for Degradation {
+    fn default() -> Degradation {
+        Degradation { threshold: 0.1, years: 3, loss: 0.1, rest: 5, recovery: 0.05 }
+    }
+}
+
+// Plants of one cell.
+#[derive(Clone)]
+pub struct Vegetation {
+    pub stands: IndexMap<Plant, Stand>,
+}
+
+impl Default for Vegetation {
+    fn default() -> Vegetation {
+        Vegetation {
+            stands: Plant::ALL.iter().map(|plant| (*plant, Stand::new(*plant, 0.0))).collect(),
+        }
+    }
+}
+
+impl Vegetation {
+    // Sets the most fodder of `plant`, and fills it up.
+    pub fn plant(&mut self, plant: Plant, f_max: f32) {
+        let stand = self.stands.get_mut(&plant).expect("Expected Plant");
+        stand.f_max = f_max;
+        stand.fodder = f_max;
+        stand.potential = f_max;
+    }
+
+    pub fn fodder(&self) -> f32 {
+        self.stands.values().map(|stand| stand.fodder).sum()
+    }
+
+    pub fn f_max(&self) -> f32 {
+        self.stands.values().map(|stand| stand.f_max).sum()
+    }
+
+    // Food that `species` can graze.
+    pub fn pasture(&self, species: &Species) -> f32 {
+        self.stands.iter()
+            .map(|(plant, stand)| plant.palatability(species) * stand.fodder)
+            .sum()
+    }
+
+    // Removes the fodder that gave `species` the food `eaten`, from the most palatable plants
+    // first.
+    pub fn crop(&mut self, species: &Species, mut eaten: f32) {
+        let mut plants: Vec<(Plant, f32)> = Plant::ALL.iter()
+            .map(|plant| (*plant, plant.palatability(species)))
+            .filter(|(_, palatability)| *palatability > 0.0)
+            .collect();
+        plants.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        for (plant, palatability) in plants {
+            if eaten <= 0.0 {
+                break;
+            }
+            let stand = self.stands.get_mut(&plant).expect("Expected Plant");
+            let food = eaten.min(palatability * stand.fodder);
+            stand.fodder = (stand.fodder - food / palatability).max(0.0);
+            eaten -= food;
+        }
+    }
+
+    // Grows every plant with its growth model, up to its most fodder less the capacity taken up by
+    // the fodder of the other plants at the start of the year.
+    pub fn grow(&mut self, rng: &mut StdRng) {
+        let fodder: Vec<f32> = Plant::ALL.iter().map(|plant| self.stands[plant].fodder).collect();
+        for (plant, stand) in self.stands.iter_mut() {
+            let parameters = plant.parameters();
+            let shaded: f32 = parameters.competition.iter()
+                .zip(fodder.iter())
+                .map(|(competition, fodder)| competition * fodder)
+                .sum();
+            let capacity = (stand.f_max - shaded).max(0.0);
+            stand.fodder = stand.growth.grow(stand, capacity, rng).clamp(0.0, stand.f_max);
+        }
+    }
+
+    // Degrades or recovers the land of each plant after a year that it was `grazed` or not.
+    pub fn degrade(&mut self, degradation: &Degradation, grazed: bool) {
+        for stand in self.stands.values_mut() {
+            if stand.potential == 0.0 {
+                continue
+            }
+            if grazed && stand.fodder < degradation.threshold * stand.f_max {
+                stand.overgrazed += 1;
+                stand.rested = 0;
+                if stand.overgrazed > degradation.years {
+                    stand.f_max -= degradation.loss * stand.f_max;
+                    stand.fodder = stand.fodder.min(stand.f_max);
+                }
+            } else {
+                stand.overgrazed = 0;
+                stand.rested += 1;
+                if stand.rested > degradation.rest {
+                    stand.f_max = f32::min(stand.potential, stand.f_max + degradation.recovery * stand.potential);
+                }
+            }
+        }
+    }
+
+    // Adds `fodder`, shared between the plants by their most fodder.
+    pub fn enrich(&mut self, fodder: f32) {
+        let f_max = self.f_max();
+        if f_max == 0.0 {
+            return
+        }
+        for stand in self.stands.values_mut() {
+            stand.fodder = f32::min(stand.f_max, stand.fodder + fodder * stand.f_max / f_max);
+        }
+    }
+}
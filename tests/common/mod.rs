@@ -0,0 +1,23 @@
+// Setup shared by the integration tests, each of which uses only part of it.
+#![allow(dead_code)]
+
+use ecosystem_rust::island::Island;
+
+// Lowland and highland cells, all reachable from each other.
+pub const GEOGRAPHY: [&str; 4] = [
+    "WWWWW",
+    "WLLHW",
+    "WHLLW",
+    "WWWWW"
+];
+
+// Island without animals, which checks its invariants after every phase.
+pub fn island_on(geography: Vec<&'static str>, seed: u64) -> Island<'static> {
+    let mut isl = Island::new(geography, seed).unwrap();
+    isl.checking = true;
+    isl
+}
+
+pub fn island(seed: u64) -> Island<'static> {
+    island_on(GEOGRAPHY.to_vec(), seed)
+}
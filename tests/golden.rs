@@ -0,0 +1,187 @@
+// Golden-run regression tests.
+//
+// Each case is a seeded simulation whose yearly population is stored in `tests/golden/`. A run
+// that differs from its baseline in any year fails. After an intentional change of behaviour,
+// update the baselines with:
+//
+//     cargo test --test golden -- --bless
+//
+// Every case is also run in parallel and with the `Columns` layout, which must give the same
+// result as the serial run with `Structs`.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use ecosystem_rust::animals::Species;
+use ecosystem_rust::herd::Layout;
+use ecosystem_rust::simulation::Simulation;
+use ecosystem_rust::topology::Grid;
+
+struct Case {
+    name: &'static str,
+    geography: &'static [&'static str],
+    grid: Grid,
+    toroidal: bool,
+    seed: u64,
+    years: u16,
+    population: &'static [((usize, usize), Species, u16)],
+}
+
+const SMALL: &[&str] = &[
+    "WWWWW",
+    "WLLLW",
+    "WLHLW",
+    "WLLLW",
+    "WWWWW",
+];
+
+const ISLAND: &[&str] = &[
+    "WWWWWWWWWWWWW",
+    "WWWLHHWWWHHWW",
+    "WWLLLHWLWHLLW",
+    "WWLLLLLLLMLMW",
+    "WWHHLLLHLHMMW",
+    "WHHLLLHWHHLMW",
+    "WWWHHWWWWWMWW",
+    "WWWWWWWWWWWWW",
+];
+
+const HEXAGONAL: &[&str] = &[
+    "WWWWWWW",
+    " WLLHLWW",
+    "WLHLLLW",
+    " WLLMLWW",
+    "WWWWWWW",
+];
+
+const TORUS: &[&str] = &[
+    "LLHL",
+    "LHLL",
+    "LLLM",
+    "HLLL",
+];
+
+const CASES: &[Case] = &[
+    Case {
+        name: "small",
+        geography: SMALL, grid: Grid::Square4, toroidal: false,
+        seed: 1, years: 60,
+        population: &[((2, 2), Species::Herbivore, 50), ((2, 2), Species::Carnivore, 5)],
+    },
+    Case {
+        name: "island",
+        geography: ISLAND, grid: Grid::Square4, toroidal: false,
+        seed: 2024, years: 60,
+        population: &[((2, 2), Species::Herbivore, 100), ((2, 2), Species::Carnivore, 10)],
+    },
+    Case {
+        name: "hexagonal",
+        geography: HEXAGONAL, grid: Grid::Hexagonal, toroidal: false,
+        seed: 7, years: 60,
+        population: &[((2, 2), Species::Herbivore, 50), ((2, 2), Species::Carnivore, 5)],
+    },
+    Case {
+        name: "torus",
+        geography: TORUS, grid: Grid::Square8, toroidal: true,
+        seed: 42, years: 60,
+        population: &[((0, 0), Species::Herbivore, 50), ((0, 0), Species::Carnivore, 5)],
+    },
+];
+
+// Yearly population of `case`, one line per year: "year herbivores carnivores".
+fn run(case: &Case, parallel: bool, layout: Layout) -> String {
+    let mut sim = Simulation::with_topology(
+        case.geography.to_vec(), case.seed, case.grid, case.toroidal, "golden.png"
+    ).unwrap();
+    sim.island.parallel = parallel;
+    sim.island.layout = layout;
+    sim.add_population(case.population.to_vec()).unwrap();
+    sim.simulate(case.years, false).unwrap();
+
+    let population = sim.population();
+    (0..=case.years as usize)
+        .map(|year| format!(
+            "{} {} {}\n",
+            year,
+            population[&Species::Herbivore][year],
+            population[&Species::Carnivore][year],
+        ))
+        .collect()
+}
+
+fn baseline(case: &Case) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{}.txt", case.name))
+}
+
+// First line where `actual` differs from `expected`.
+fn difference(expected: &str, actual: &str) -> Option<String> {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return None,
+            (e, a) if e == a => continue,
+            (e, a) => return Some(format!(
+                "expected `{}`, got `{}`", e.unwrap_or("<end>"), a.unwrap_or("<end>")
+            )),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let bless = arguments.iter().any(|argument| argument == "--bless");
+    let filters: Vec<&String> = arguments.iter().filter(|argument| !argument.starts_with('-')).collect();
+
+    let mut failures = 0;
+    for case in CASES {
+        if !filters.is_empty() && !filters.iter().any(|filter| case.name.contains(filter.as_str())) {
+            continue
+        }
+        let actual = run(case, false, Layout::Structs);
+        let path = baseline(case);
+
+        let mut problems = Vec::new();
+        for (mode, parallel, layout) in [
+            ("parallel", true, Layout::Structs),
+            ("columns", false, Layout::Columns),
+        ] {
+            if let Some(problem) = difference(&actual, &run(case, parallel, layout)) {
+                problems.push(format!("{} run differs from serial run: {}", mode, problem));
+            }
+        }
+
+        if bless {
+            fs::write(&path, &actual).expect("Failed to write the baseline");
+        } else {
+            match fs::read_to_string(&path) {
+                Ok(expected) => {
+                    if let Some(problem) = difference(&expected, &actual) {
+                        problems.push(format!("differs from {}: {}", path.display(), problem));
+                    }
+                }
+                Err(_) => problems.push(format!("missing baseline {}", path.display())),
+            }
+        }
+
+        if problems.is_empty() {
+            println!("golden {} ... ok", case.name);
+        } else {
+            failures += 1;
+            println!("golden {} ... FAILED", case.name);
+            for problem in problems {
+                println!("    {}", problem);
+            }
+        }
+    }
+
+    if failures > 0 {
+        println!("\n{} golden run(s) failed; if the change is intended, run", failures);
+        println!("    cargo test --test golden -- --bless");
+        return ExitCode::FAILURE
+    }
+    ExitCode::SUCCESS
+}
@@ -1,21 +1,15 @@
-#[path = "../src/animals.rs"] mod animals;
-
 #[cfg(test)]
 mod tests {
-    use crate::animals::*;
-    use rand::rngs::ThreadRng;
+    use ecosystem_rust::animals::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn test_birthweight() {
-        let mut rng = ThreadRng::default();
-        let animal = Animal {
-            species: Species::Herbivore,
-            weight: 10.0,
-            age: 5,
-            fitness: 0.5,
-        };
-        let birthweight = animal.birthweight(&mut rng);
-        assert!(birthweight > 0.0);
+        let mut rng = StdRng::seed_from_u64(1);
+        for species in Species::ALL {
+            assert!(birthweight(species, &mut rng).unwrap() > 0.0);
+        }
     }
 
     #[test]
@@ -25,9 +19,10 @@ mod tests {
             weight: 10.0,
             age: 5,
             fitness: 0.5,
+            health: Health::Susceptible,
         };
-        animal.eat(5);
-        assert_eq!(animal.weight, 15.0);
+        animal.eat(5.0);
+        assert_eq!(animal.weight, 10.0 + Parameters::HERBIVORE.beta * 5.0);
     }
 
     #[test]
@@ -37,6 +32,7 @@ mod tests {
             weight: 10.0,
             age: 5,
             fitness: 0.5,
+            health: Health::Susceptible,
         };
         animal.aging();
         assert_eq!(animal.age, 6);
@@ -49,6 +45,7 @@ mod tests {
             weight: 10.0,
             age: 5,
             fitness: 0.5,
+            health: Health::Susceptible,
         };
         animal.lose_weight_year();
         assert_eq!(animal.weight, 10.0 - Parameters::HERBIVORE.eta * 10.0);
@@ -61,9 +58,10 @@ mod tests {
             weight: 10.0,
             age: 5,
             fitness: 0.5,
+            health: Health::Susceptible,
         };
         let result = animal.lose_weight_birth(5.0);
-        assert_eq!(result, true);
+        assert!(result);
         assert_eq!(animal.weight, 10.0 - Parameters::HERBIVORE.xi * 5.0);
     }
 
@@ -74,6 +72,7 @@ mod tests {
             weight: 10.0,
             age: 5,
             fitness: 0.5,
+            health: Health::Susceptible,
         };
         animal.calculate_fitness();
         assert!(animal.fitness > 0.0);
@@ -86,20 +85,25 @@ mod tests {
             weight: 10.0,
             age: 5,
             fitness: 0.5,
+            health: Health::Susceptible,
         };
-        let eaten = animal.graze(30);
+        let eaten = animal.graze(30.0).unwrap();
         assert_eq!(eaten, Parameters::HERBIVORE.hunger);
-        assert_eq!(animal.weight, 10.0 + Parameters::HERBIVORE.hunger as f32);
+        assert_eq!(animal.weight, 10.0 + Parameters::HERBIVORE.beta * Parameters::HERBIVORE.hunger);
+
+        let eaten = animal.graze(5.0).unwrap();
+        assert_eq!(eaten, 5.0);
     }
 
     #[test]
     fn test_predation() {
-        let mut rng = ThreadRng::default();
+        let mut rng = StdRng::seed_from_u64(1);
         let mut animal = Animal {
             species: Species::Carnivore,
             weight: 10.0,
             age: 5,
             fitness: 0.5,
+            health: Health::Susceptible,
         };
         let mut herbivores = vec![
             Animal {
@@ -107,16 +111,75 @@ mod tests {
                 weight: 5.0,
                 age: 3,
                 fitness: 0.3,
+                health: Health::Susceptible,
             },
             Animal {
                 species: Species::Herbivore,
                 weight: 7.0,
                 age: 4,
                 fitness: 0.4,
+                health: Health::Susceptible,
             },
         ];
-        let eaten = animal.predation(&mut rng, &mut herbivores);
-        assert!(eaten <= Parameters::CARNIVORE.hunger);
+        let hunt = animal.predation(&mut rng, &mut herbivores).unwrap();
+        assert!(hunt.kills <= hunt.attempts);
+        assert!((0.0..=12.0).contains(&(hunt.eaten + hunt.leftovers)));
+        assert_eq!(herbivores.len() as u32, 2 - hunt.kills);
         assert!(animal.weight >= 10.0);
+        assert!(animal.weight <= 10.0 + Parameters::CARNIVORE.beta * Parameters::CARNIVORE.hunger);
+    }
+
+    #[test]
+    fn test_omnivore_grazes_then_hunts() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut animal = Animal {
+            species: Species::Omnivore,
+            weight: 10.0,
+            age: 5,
+            fitness: 0.5,
+            health: Health::Susceptible,
+        };
+        let appetite = Parameters::OMNIVORE.hunger * Parameters::OMNIVORE.grazing;
+        let eaten = animal.graze(100.0).unwrap();
+        assert_eq!(eaten, appetite);
+
+        let mut herbivores = vec![Animal {
+            species: Species::Herbivore,
+            weight: 50.0,
+            age: 3,
+            fitness: 0.0,
+            health: Health::Susceptible,
+        }];
+        let before = animal.weight;
+        let rest = Parameters::OMNIVORE.hunger - eaten;
+        let hunt = animal.hunt(&mut rng, &mut herbivores, rest, Parameters::OMNIVORE.delta_phi_max).unwrap();
+        assert!(hunt.eaten <= rest);
+        assert!(animal.weight <= before + Parameters::OMNIVORE.beta * (Parameters::OMNIVORE.hunger - eaten));
+    }
+
+    #[test]
+    fn test_diets() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut carnivore = Animal { species: Species::Carnivore, weight: 10.0, age: 5, fitness: 0.5, health: Health::Susceptible };
+        let mut herbivore = Animal { species: Species::Herbivore, weight: 10.0, age: 5, fitness: 0.5, health: Health::Susceptible };
+        assert!(carnivore.graze(10.0).is_err());
+        assert!(herbivore.predation(&mut rng, &mut Vec::new()).is_err());
+        assert!(carnivore.scavenge(10.0).is_err());
+    }
+
+    #[test]
+    fn test_scavenge() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut animal = Animal {
+            species: Species::Scavenger,
+            weight: 10.0,
+            age: 5,
+            fitness: 0.5,
+            health: Health::Susceptible,
+        };
+        assert_eq!(animal.scavenge(100.0).unwrap(), Parameters::SCAVENGER.hunger);
+        assert_eq!(animal.scavenge(5.0).unwrap(), 5.0);
+        assert!(animal.graze(10.0).is_err());
+        assert!(animal.predation(&mut rng, &mut Vec::new()).is_err());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore};
+    use ecosystem_rust::archipelago::*;
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::simulation::*;
+    use indexmap::IndexMap;
+
+    const GEOGRAPHY: [&str; 7] = [
+        "WWWWWWWWWW",
+        "WLLWWWWHHW",
+        "WLLWWWWHHW",
+        "WWWWWWWWWW",
+        "WWWWLWWWWW",
+        "WWWWWLWWWW",
+        "WWWWWWWWWW",
+    ];
+
+    #[test]
+    fn test_islands_are_connected_land() {
+        let isl = Island::new(GEOGRAPHY.to_vec(), 1).unwrap();
+        // The two diagonal cells are separate islands on a four-neighbour grid.
+        assert_eq!(isl.archipelago.count(), 4);
+        assert_eq!(isl.archipelago.island(&(1, 1)), isl.archipelago.island(&(2, 2)));
+        assert_ne!(isl.archipelago.island(&(1, 1)), isl.archipelago.island(&(1, 7)));
+        assert_ne!(isl.archipelago.island(&(4, 4)), isl.archipelago.island(&(5, 5)));
+        assert_eq!(isl.archipelago.island(&(0, 0)), None);
+    }
+
+    #[test]
+    fn test_water_is_a_barrier_by_default() {
+        let mut isl = Island::new(GEOGRAPHY.to_vec(), 2).unwrap();
+        isl.add_population(vec![
+            ((1, 1), Herbivore, 100),
+            ((1, 1), Carnivore, 100)
+        ]).unwrap();
+        let home = isl.archipelago.island(&(1, 1)).unwrap();
+        for _ in 0..5 {
+            isl.migrate();
+        }
+        for (island, species) in isl.animals_per_island() {
+            if island != home {
+                assert_eq!(species[&Herbivore] + species[&Carnivore], 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_crossing_reaches_other_islands() {
+        let mut isl = Island::new(GEOGRAPHY.to_vec(), 3).unwrap();
+        isl.crossing.insert(Herbivore, Crossing { probability: 1.0, distance: 6 });
+        isl.add_population(vec![((1, 1), Herbivore, 200)]).unwrap();
+        let (before, _) = isl.animals();
+        isl.migrate();
+        let (after, _) = isl.animals();
+        assert_eq!(before, after);
+
+        let home = isl.archipelago.island(&(1, 1)).unwrap();
+        let abroad: u32 = isl.animals_per_island().iter()
+            .filter(|(island, _)| **island != home)
+            .map(|(_, species)| species[&Herbivore])
+            .sum();
+        assert!(abroad > 0);
+    }
+
+    #[test]
+    fn test_crossing_respects_distance() {
+        let mut isl = Island::new(GEOGRAPHY.to_vec(), 4).unwrap();
+        isl.crossing.insert(Carnivore, Crossing { probability: 1.0, distance: 3 });
+        isl.add_population(vec![((2, 2), Carnivore, 200)]).unwrap();
+        isl.migrate();
+        // Only the island at (4, 4) is within three cells of (2, 2).
+        let islands = isl.animals_per_island();
+        assert!(islands[&isl.archipelago.island(&(4, 4)).unwrap()][&Carnivore] > 0);
+        assert_eq!(islands[&isl.archipelago.island(&(1, 7)).unwrap()][&Carnivore], 0);
+        assert_eq!(islands[&isl.archipelago.island(&(5, 5)).unwrap()][&Carnivore], 0);
+    }
+
+    #[test]
+    fn test_species_without_crossing_stay_home() {
+        let mut isl = Island::new(GEOGRAPHY.to_vec(), 6).unwrap();
+        isl.crossing = IndexMap::from([(Herbivore, Crossing { probability: 1.0, distance: 6 })]);
+        isl.add_population(vec![((1, 1), Carnivore, 200)]).unwrap();
+        let home = isl.archipelago.island(&(1, 1)).unwrap();
+        isl.migrate();
+        for (island, species) in isl.animals_per_island() {
+            if island != home {
+                assert_eq!(species[&Carnivore], 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_simulation_records_islands() {
+        let mut sim = Simulation::new(GEOGRAPHY.to_vec(), 5, "unused.png").unwrap();
+        sim.add_population(vec![((1, 7), Herbivore, 20)]).unwrap();
+        sim.simulate(3, false).unwrap();
+        assert_eq!(sim.islands().len(), 4);
+        for species in sim.islands().values() {
+            assert_eq!(species[&Herbivore].len(), 4);
+        }
+    }
+}
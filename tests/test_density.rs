@@ -0,0 +1,83 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore};
+    use ecosystem_rust::density::*;
+    use ecosystem_rust::island::*;
+    use indexmap::IndexMap;
+    use crate::common::island;
+
+    fn density(capacity: f32, crowding: f32) -> Density {
+        Density { capacity: IndexMap::from([('L', capacity), ('H', capacity)]), crowding }
+    }
+
+    fn herbivores(isl: &Island) -> Vec<usize> {
+        isl.cells().values().map(|cell| cell.animals[&Herbivore].len()).collect()
+    }
+
+    #[test]
+    fn test_factors() {
+        let regulation = density(40.0, 0.5);
+        assert_eq!(regulation.birth(b'L', 10), 0.75);
+        assert_eq!(regulation.birth(b'L', 60), 0.0);
+        assert_eq!(regulation.death(b'L', 40), 0.0);
+        assert_eq!(regulation.death(b'L', 80), 0.25);
+
+        // Desert has no capacity and is not regulated.
+        assert_eq!(regulation.birth(b'D', 1000), 1.0);
+        assert_eq!(regulation.death(b'D', 1000), 0.0);
+    }
+
+    #[test]
+    fn test_invalid_density() {
+        assert!(density(-1.0, 0.5).validate().is_err());
+        assert!(density(40.0, 1.5).validate().is_err());
+
+        let mut isl = island(1);
+        isl.density.insert(Herbivore, density(f32::NAN, 0.5));
+        assert!(isl.procreate().is_err());
+        assert!(isl.aging().is_err());
+    }
+
+    #[test]
+    fn test_population_is_held_near_capacity() {
+        let run = |regulation: Density| {
+            let mut isl = island(2);
+            isl.add_population(vec![((1, 1), Herbivore, 50)]).unwrap();
+            isl.density.insert(Herbivore, regulation);
+            for _ in 0..40 {
+                isl.yearly_cycle().unwrap();
+            }
+            herbivores(&isl)
+        };
+        let regulated = run(density(30.0, 0.5));
+        let unregulated = run(Density::default());
+        assert!(regulated.iter().sum::<usize>() < unregulated.iter().sum::<usize>());
+        assert!(regulated.iter().all(|n| *n <= 45));
+        assert!(regulated.iter().any(|n| *n >= 10));
+    }
+
+    #[test]
+    fn test_crowding_thins_full_cells() {
+        let mut isl = island(3);
+        isl.add_population(vec![((1, 1), Herbivore, 200)]).unwrap();
+        isl.density.insert(Herbivore, density(20.0, 1.0));
+        isl.aging().unwrap();
+        assert!(isl.cells()[&(1, 1)].animals[&Herbivore].len() < 100);
+    }
+
+    #[test]
+    fn test_species_without_regulation() {
+        let mut isl = island(4);
+        isl.add_population(vec![
+            ((1, 1), Herbivore, 50),
+            ((1, 1), Carnivore, 10)
+        ]).unwrap();
+        isl.density = IndexMap::from([(Herbivore, density(30.0, 0.5))]);
+        for _ in 0..10 {
+            isl.yearly_cycle().unwrap();
+        }
+        assert!(herbivores(&isl).iter().all(|n| *n <= 45));
+    }
+}
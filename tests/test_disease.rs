@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::Health;
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore};
+    use ecosystem_rust::disease::*;
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::simulation::*;
+    use ecosystem_rust::Error;
+
+    fn geography() -> Vec<&'static str> {
+        vec![
+            "WWWWWW",
+            "WLLLLW",
+            "WLLLLW",
+            "WWWWWW",
+        ]
+    }
+
+    #[test]
+    fn test_infection_grows_with_infected() {
+        let mut disease = Disease::default();
+        disease.susceptibility.shift_remove(&Carnivore);
+        assert_eq!(disease.infection(&Herbivore, 0), 0.0);
+        assert!(disease.infection(&Herbivore, 1) < disease.infection(&Herbivore, 10));
+        assert_eq!(disease.infection(&Carnivore, 10), 0.0);
+    }
+
+    #[test]
+    fn test_invalid_disease() {
+        let mut isl = Island::new(geography(), 1).unwrap();
+        isl.disease = Some(Disease { recovery: 1.5, ..Disease::default() });
+        assert!(matches!(isl.spread_disease(), Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_infected_lose_weight() {
+        let mut isl = Island::new(geography(), 1).unwrap();
+        isl.disease = Some(Disease { transmission: 0.0, recovery: 0.0, virulence: 0.5, ..Disease::default() });
+        isl.add_population(vec![((1, 1), Herbivore, 10)]).unwrap();
+        isl.infect(vec![((1, 1), Herbivore, 4)]).unwrap();
+
+        let before: Vec<f32> = isl.cells()[&(1, 1)].animals[&Herbivore].iter().map(|a| a.weight).collect();
+        isl.spread_disease().unwrap();
+        for (animal, weight) in isl.cells()[&(1, 1)].animals[&Herbivore].iter().zip(before) {
+            match animal.health {
+                Health::Infected => assert_eq!(animal.weight, 0.5 * weight),
+                _ => assert_eq!(animal.weight, weight),
+            }
+        }
+        assert_eq!(isl.epidemic().infected, 4);
+    }
+
+    #[test]
+    fn test_outbreak_spreads_and_migrates() {
+        let mut isl = Island::new(geography(), 2).unwrap();
+        isl.checking = true;
+        isl.disease = Some(Disease { transmission: 0.1, ..Disease::default() });
+        isl.add_population(vec![((1, 1), Herbivore, 200)]).unwrap();
+        isl.infect(vec![((1, 1), Herbivore, 5)]).unwrap();
+
+        isl.migrate();
+        let infected = isl.cells().iter()
+            .filter(|(coordinate, _)| **coordinate != (1, 1))
+            .flat_map(|(_, cell)| cell.animals[&Herbivore].iter())
+            .filter(|animal| animal.health == Health::Infected)
+            .count();
+        assert_eq!(isl.epidemic().infected, 5);
+        assert!(infected > 0);
+
+        isl.spread_disease().unwrap();
+        let epidemic = isl.epidemic();
+        assert!(epidemic.infections > 0);
+        assert_eq!(epidemic.susceptible + epidemic.infected + epidemic.recovered, 200);
+        assert!(epidemic.prevalence() > 0.0);
+    }
+
+    #[test]
+    fn test_yearly_epidemic() {
+        let mut sim = Simulation::new(geography(), 3, "unused.png").unwrap();
+        sim.island.disease = Some(Disease::default());
+        sim.add_population(vec![((1, 1), Herbivore, 100), ((2, 2), Carnivore, 10)]).unwrap();
+        sim.island.infect(vec![((1, 1), Herbivore, 10)]).unwrap();
+        sim.simulate(10, false).unwrap();
+
+        let epidemic = sim.epidemic();
+        assert_eq!(epidemic.len(), 11);
+        assert_eq!(epidemic[0].infected, 10);
+        assert!(epidemic.iter().map(|year| year.infections).sum::<u64>() > 0);
+    }
+}
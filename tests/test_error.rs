@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::*;
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::simulation::*;
+    use ecosystem_rust::validation::*;
+    use ecosystem_rust::Error;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn geography() -> Vec<&'static str> {
+        vec![
+            "WWWW",
+            "WLHW",
+            "WWWW",
+        ]
+    }
+
+    #[test]
+    fn test_invalid_geography() {
+        assert!(matches!(
+            Island::new(vec!["WWW", "WXW", "WWW"], 1),
+            Err(Error::InvalidTerrain { cell: (1, 1), terrain: 'X' })
+        ));
+        assert!(matches!(Island::new(vec!["WWW", "WW", "WWW"], 1), Err(Error::InvalidGeography(_))));
+        assert!(matches!(Island::new(vec![], 1), Err(Error::InvalidGeography(_))));
+        assert!(matches!(Island::new(vec!["WWW", "WLL", "WWW"], 1), Err(Error::InvalidGeography(_))));
+    }
+
+    #[test]
+    fn test_invalid_coordinate() {
+        let mut isl = Island::new(geography(), 1).unwrap();
+        let result = isl.add_population(vec![
+            ((1, 1), Species::Herbivore, 5),
+            ((7, 1), Species::Herbivore, 5)
+        ]);
+        assert!(matches!(result, Err(Error::InvalidCoordinate((7, 1)))));
+        // Nothing is added when any coordinate is invalid.
+        assert!(isl.inhabited().is_empty());
+    }
+
+    #[test]
+    fn test_animals_cannot_be_placed_on_water() {
+        let mut isl = Island::new(geography(), 1).unwrap();
+        let result = isl.add_population(vec![
+            ((1, 1), Species::Herbivore, 5),
+            ((0, 1), Species::Herbivore, 5)
+        ]);
+        assert!(matches!(result, Err(Error::InvalidTerrain { cell: (0, 1), terrain: 'W' })));
+        assert!(isl.inhabited().is_empty());
+    }
+
+    #[test]
+    fn test_species_mismatch() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut carnivore = Animal { species: Species::Carnivore, weight: 10.0, age: 1, fitness: 0.5, health: Health::Susceptible };
+        let mut herbivore = Animal { species: Species::Herbivore, weight: 10.0, age: 1, fitness: 0.5, health: Health::Susceptible };
+
+        let error = carnivore.graze(10.0).unwrap_err();
+        assert!(matches!(error, Error::SpeciesMismatch { species: Species::Carnivore, action: "graze" }));
+        assert_eq!(error.to_string(), "Carnivores can't graze");
+        assert!(matches!(
+            herbivore.predation(&mut rng, &mut Vec::new()),
+            Err(Error::SpeciesMismatch { species: Species::Herbivore, .. })
+        ));
+    }
+
+    #[test]
+    fn test_render_failure() {
+        let sim = Simulation::new(geography(), 1, "unused.png").unwrap();
+        assert!(matches!(sim.map("/nonexistent/directory/map.png"), Err(Error::Render(_))));
+
+        // There is nothing to graph before the simulation has run.
+        let sim = Simulation::new(geography(), 1, "unused.png").unwrap();
+        assert!(matches!(sim.graph(), Err(Error::Render(_))));
+    }
+
+    #[test]
+    fn test_io_and_data_failure() {
+        assert!(matches!(load_reference("/nonexistent/reference.csv"), Err(Error::Io(_))));
+        assert!(matches!(
+            read_reference("replicate,year,herbivores,carnivores\n0,0,10\n"),
+            Err(Error::InvalidData(_))
+        ));
+        assert!(matches!(
+            read_reference("replicate,year,herbivores,carnivores\n0,1,10,2\n"),
+            Err(Error::InvalidData(_))
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::Species::{self, Apex, Carnivore, Herbivore, Omnivore, Scavenger};
+    use ecosystem_rust::foodweb::*;
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::Error;
+
+    #[test]
+    fn test_default_order() {
+        let web = FoodWeb::default();
+        assert_eq!(web.order(), &[Herbivore, Carnivore, Omnivore, Apex, Scavenger]);
+        assert_eq!(web.level(Herbivore), 0);
+        assert_eq!(web.level(Carnivore), 1);
+        assert_eq!(web.level(Apex), 2);
+    }
+
+    #[test]
+    fn test_invalid_webs() {
+        assert!(matches!(
+            FoodWeb::new(vec![Link::new(Carnivore, Carnivore)]),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            FoodWeb::new(vec![Link::new(Herbivore, Carnivore)]),
+            Err(Error::SpeciesMismatch { species: Herbivore, .. })
+        ));
+        assert!(matches!(
+            FoodWeb::new(vec![Link { preference: 0.0, ..Link::new(Apex, Carnivore) }]),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            FoodWeb::new(vec![Link::new(Apex, Carnivore), Link::new(Carnivore, Apex)]),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_chain_of_hunters() {
+        let web = FoodWeb::new(vec![
+            Link::new(Carnivore, Herbivore),
+            Link::new(Omnivore, Carnivore),
+            Link::new(Apex, Omnivore),
+        ]).unwrap();
+        assert_eq!(web.order(), &[Herbivore, Carnivore, Omnivore, Apex, Scavenger]);
+        assert_eq!(web.level(Apex), 3);
+        assert_eq!(web.prey(Omnivore), vec![&Link::new(Omnivore, Carnivore)]);
+    }
+
+    fn island(seed: u64) -> Island<'static> {
+        let geography: Vec<&str> = vec![
+            "WWWWW",
+            "WLLLW",
+            "WLLLW",
+            "WWWWW"
+        ];
+        let mut isl = Island::new(geography, seed).unwrap();
+        isl.checking = true;
+        isl.add_population(vec![
+            ((1, 1), Carnivore, 20),
+            ((1, 1), Apex, 5)
+        ]).unwrap();
+        isl
+    }
+
+    fn weights(isl: &Island, species: Species) -> Vec<f32> {
+        isl.cells()[&(1, 1)].animals[&species].iter().map(|animal| animal.weight).collect()
+    }
+
+    #[test]
+    fn test_apex_hunts_carnivores() {
+        let mut isl = island(4);
+        isl.food_web = FoodWeb::new(vec![
+            Link::new(Carnivore, Herbivore),
+            Link { delta_phi_max: 0.001, ..Link::new(Apex, Carnivore) },
+        ]).unwrap();
+        isl.feed().unwrap();
+        assert!(isl.animals().0[&Carnivore] < 20);
+        assert!(isl.cells()[&(1, 1)].carcass >= 0.0);
+    }
+
+    #[test]
+    fn test_apex_without_prey_does_not_eat() {
+        let mut isl = island(5);
+        isl.food_web = FoodWeb::new(vec![Link::new(Carnivore, Herbivore)]).unwrap();
+        let before = weights(&isl, Apex);
+        isl.feed().unwrap();
+        assert_eq!(weights(&isl, Apex), before);
+        assert_eq!(isl.animals().0[&Carnivore], 20);
+    }
+
+    #[test]
+    fn test_food_web_keeps_invariants() {
+        let mut isl = island(6);
+        isl.add_population(vec![
+            ((1, 2), Herbivore, 80),
+            ((2, 2), Omnivore, 10),
+            ((2, 3), Scavenger, 10)
+        ]).unwrap();
+        for _ in 0..30 {
+            isl.yearly_cycle().unwrap();
+        }
+    }
+}
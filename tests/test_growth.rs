@@ -0,0 +1,62 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use ecosystem_rust::animals::Species::Herbivore;
+    use ecosystem_rust::growth::*;
+    use ecosystem_rust::vegetation::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::common::island;
+
+    fn stand(f_max: f32, fodder: f32, growth: Arc<dyn GrowthModel>) -> Stand {
+        Stand { fodder, growth, ..Stand::new(Plant::Grass, f_max) }
+    }
+
+    #[test]
+    fn test_models() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let regrowth = Regrowth { v_max: 100.0, alpha: 0.5 };
+        assert_eq!(regrowth.grow(&stand(400.0, 200.0, Arc::new(Reset)), 400.0, &mut rng), 275.0);
+        assert_eq!(Reset.grow(&stand(400.0, 0.0, Arc::new(Reset)), 300.0, &mut rng), 300.0);
+        assert_eq!(Linear { rate: 50.0 }.grow(&stand(400.0, 380.0, Arc::new(Reset)), 400.0, &mut rng), 400.0);
+
+        let logistic = Logistic { rate: 0.5, seed: 0.1 };
+        assert_eq!(logistic.grow(&stand(400.0, 200.0, Arc::new(Reset)), 400.0, &mut rng), 250.0);
+        assert!(logistic.grow(&stand(400.0, 0.0, Arc::new(Reset)), 400.0, &mut rng) > 40.0);
+    }
+
+    #[test]
+    fn test_weather_varies_growth() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let weather = Weather { model: Arc::new(Linear { rate: 100.0 }), sigma: 0.5 };
+        let grown: Vec<f32> = (0..100)
+            .map(|_| weather.grow(&stand(800.0, 200.0, Arc::new(Reset)), 800.0, &mut rng))
+            .collect();
+        assert!(grown.iter().all(|fodder| (200.0..=800.0).contains(fodder)));
+        assert!(grown.iter().any(|fodder| *fodder < 300.0) && grown.iter().any(|fodder| *fodder > 300.0));
+
+        let calm = Weather { model: Arc::new(Linear { rate: 100.0 }), sigma: 0.0 };
+        assert_eq!(calm.grow(&stand(800.0, 200.0, Arc::new(Reset)), 800.0, &mut rng), 300.0);
+    }
+
+    #[test]
+    fn test_growth_per_terrain() {
+        let mut isl = island(8);
+        isl.set_growth('H', Plant::Grass, Arc::new(Linear { rate: 10.0 })).unwrap();
+        isl.set_growth('L', Plant::Grass, Arc::new(Weather { model: Arc::new(Reset), sigma: 0.3 })).unwrap();
+        assert!(isl.set_growth('W', Plant::Grass, Arc::new(Reset)).is_err());
+        isl.add_population(vec![
+            ((1, 3), Herbivore, 50),
+            ((1, 1), Herbivore, 50)
+        ]).unwrap();
+        for _ in 0..5 {
+            isl.yearly_cycle().unwrap();
+        }
+        // Highland only grows back 10 a year.
+        assert!(isl.cells().iter()
+            .filter(|((i, j), _)| isl.geography[*i][*j] == b'H')
+            .any(|(_, cell)| cell.fodder() < 300.0));
+    }
+}
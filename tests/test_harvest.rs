@@ -0,0 +1,157 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore};
+    use ecosystem_rust::error::Error;
+    use ecosystem_rust::harvest::*;
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::simulation::Simulation;
+    use indexmap::IndexMap;
+    use crate::common::{island, GEOGRAPHY};
+
+    fn harvesting(season: Season, policy: Policy, selection: Selection) -> Harvesting {
+        Harvesting {
+            season,
+            harvests: IndexMap::from([(Herbivore, Harvest { policy, selection })]),
+            protected: Vec::new(),
+        }
+    }
+
+    fn herbivores(isl: &Island, coordinate: (usize, usize)) -> Vec<f32> {
+        isl.cells()[&coordinate].animals[&Herbivore].iter().map(|animal| animal.weight).collect()
+    }
+
+    #[test]
+    fn test_amounts() {
+        let quota = Harvest { policy: Policy::Quota(10), selection: Selection::Random };
+        assert_eq!(quota.amount(25), 10);
+        assert_eq!(quota.amount(4), 4);
+
+        let share = Harvest { policy: Policy::Proportional(0.25), selection: Selection::Random };
+        assert_eq!(share.amount(10), 3);
+        assert_eq!(share.amount(0), 0);
+    }
+
+    #[test]
+    fn test_invalid_harvesting() {
+        let mut isl = island(1);
+        isl.harvesting = Some(harvesting(Season::Feed, Policy::Proportional(1.5), Selection::Random));
+        assert!(isl.harvest().is_err());
+
+        let mut protected = harvesting(Season::Feed, Policy::Quota(5), Selection::Random);
+        protected.protected = vec![(9, 9)];
+        isl.harvesting = Some(protected);
+        assert!(matches!(isl.harvest(), Err(Error::InvalidCoordinate((9, 9)))));
+    }
+
+    #[test]
+    fn test_quota() {
+        let mut isl = island(2);
+        isl.add_population(vec![
+            ((1, 1), Herbivore, 60),
+            ((2, 2), Herbivore, 40),
+            ((1, 1), Carnivore, 10)
+        ]).unwrap();
+        isl.harvesting = Some(harvesting(Season::Aging, Policy::Quota(15), Selection::Random));
+        isl.harvest().unwrap();
+        assert_eq!(herbivores(&isl, (1, 1)).len() + herbivores(&isl, (2, 2)).len(), 85);
+        assert_eq!(isl.harvest_yield()[&Herbivore].animals, 15);
+        assert!(isl.harvest_yield()[&Herbivore].biomass > 0.0);
+        assert_eq!(isl.cells()[&(1, 1)].animals[&Carnivore].len(), 10);
+    }
+
+    #[test]
+    fn test_heaviest_first() {
+        let mut isl = island(3);
+        isl.add_population(vec![
+            ((1, 1), Herbivore, 60),
+            ((2, 2), Herbivore, 40)
+        ]).unwrap();
+        let mut weights: Vec<f32> = herbivores(&isl, (1, 1)).into_iter()
+            .chain(herbivores(&isl, (2, 2)))
+            .collect();
+        weights.sort_by(|a, b| b.total_cmp(a));
+
+        isl.harvesting = Some(harvesting(Season::Feed, Policy::Proportional(0.2), Selection::Heaviest));
+        isl.harvest().unwrap();
+        let harvested = isl.harvest_yield()[&Herbivore];
+        assert_eq!(harvested.animals, 20);
+        assert!((harvested.biomass - weights[..20].iter().sum::<f32>()).abs() < 1e-3);
+        assert!(herbivores(&isl, (1, 1)).into_iter()
+            .chain(herbivores(&isl, (2, 2)))
+            .all(|weight| weight <= weights[19]));
+    }
+
+    #[test]
+    fn test_protected_zones() {
+        let mut isl = island(4);
+        isl.add_population(vec![
+            ((1, 1), Herbivore, 60),
+            ((2, 2), Herbivore, 40)
+        ]).unwrap();
+        let mut policy = harvesting(Season::Feed, Policy::Proportional(1.0), Selection::Random);
+        policy.protected = vec![(1, 1)];
+        isl.harvesting = Some(policy);
+        isl.harvest().unwrap();
+        assert_eq!(herbivores(&isl, (1, 1)).len(), 60);
+        assert!(herbivores(&isl, (2, 2)).is_empty());
+        assert_eq!(isl.harvest_yield()[&Herbivore].animals, 40);
+    }
+
+    #[test]
+    fn test_yearly_yield() {
+        let mut sim = Simulation::new(GEOGRAPHY.to_vec(), 5, "unused.png").unwrap();
+        sim.island.checking = true;
+        sim.add_population(vec![((1, 1), Herbivore, 100)]).unwrap();
+        sim.island.harvesting = Some(harvesting(Season::Feed, Policy::Quota(5), Selection::Heaviest));
+        sim.simulate(10, false).unwrap();
+
+        let yields = sim.yields();
+        assert_eq!(yields.len(), 11);
+        assert!(yields[0].is_empty());
+        assert!(yields[1..].iter().all(|harvested| harvested[&Herbivore].animals == 5));
+    }
+
+    #[test]
+    fn test_yield_is_reset_every_year() {
+        let mut isl = island(7);
+        isl.add_population(vec![((1, 1), Herbivore, 60)]).unwrap();
+        isl.harvesting = Some(harvesting(Season::Feed, Policy::Quota(5), Selection::Random));
+        isl.yearly_cycle().unwrap();
+        assert_eq!(isl.harvest_yield()[&Herbivore].animals, 5);
+
+        isl.harvesting = None;
+        isl.yearly_cycle().unwrap();
+        assert!(isl.harvest_yield().is_empty());
+    }
+
+    #[test]
+    fn test_harvests_of_one_year_are_counted_once() {
+        let mut isl = island(8);
+        isl.add_population(vec![((1, 1), Herbivore, 60)]).unwrap();
+        isl.profiling = true;
+        isl.harvesting = Some(harvesting(Season::Feed, Policy::Quota(5), Selection::Random));
+        isl.harvest().unwrap();
+        isl.harvest().unwrap();
+        assert_eq!(isl.harvest_yield()[&Herbivore].animals, 10);
+        assert_eq!(isl.profile.counts.harvested, 10);
+    }
+
+    #[test]
+    fn test_harvest_reduces_population() {
+        let run = |share: f32| {
+            let mut isl = island(6);
+            isl.add_population(vec![
+                ((1, 1), Herbivore, 60),
+                ((2, 2), Herbivore, 40)
+            ]).unwrap();
+            isl.harvesting = Some(harvesting(Season::Aging, Policy::Proportional(share), Selection::Random));
+            for _ in 0..20 {
+                isl.yearly_cycle().unwrap();
+            }
+            isl.animals().0[&Herbivore]
+        };
+        assert!(run(0.3) < run(0.0));
+    }
+}
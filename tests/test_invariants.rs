@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore};
+    use ecosystem_rust::herd::Layout;
+    use ecosystem_rust::invariants::*;
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::migration::*;
+    use ecosystem_rust::profile::Counts;
+    use ecosystem_rust::streams::Phase;
+    use ecosystem_rust::topology::Grid;
+    use ecosystem_rust::Error;
+    use rand::rngs::StdRng;
+
+    fn geography() -> Vec<&'static str> {
+        vec![
+            "WWWWWWW",
+            "WLLHMLW",
+            "WLHWLLW",
+            "WLLLHHW",
+            "WWWWWWW",
+        ]
+    }
+
+    #[test]
+    fn test_simulation_keeps_invariants() {
+        for (parallel, layout) in [(false, Layout::Structs), (true, Layout::Columns)] {
+            let mut isl = Island::new(geography(), 5).unwrap();
+            isl.checking = true;
+            isl.parallel = parallel;
+            isl.layout = layout;
+            isl.add_population(vec![
+                ((1, 1), Herbivore, 60),
+                ((3, 5), Carnivore, 8)
+            ]).unwrap();
+            for _ in 0..40 {
+                isl.yearly_cycle().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_hexagonal_torus_keeps_invariants() {
+        let geography = vec![
+            "LLHL",
+            "LHLW",
+            "LLLM",
+            "HLWL",
+        ];
+        let mut isl = Island::with_topology(geography, 5, Grid::Hexagonal, true).unwrap();
+        isl.checking = true;
+        isl.add_population(vec![
+            ((0, 0), Herbivore, 40),
+            ((0, 0), Carnivore, 5)
+        ]).unwrap();
+        for _ in 0..30 {
+            isl.yearly_cycle().unwrap();
+        }
+    }
+
+    // Broken migration strategy that sends every animal into the lake at (2, 3).
+    struct IntoWater;
+
+    impl MigrationStrategy for IntoWater {
+        fn destination(&self, _surroundings: &Surroundings, _rng: &mut StdRng) -> Option<(usize, usize)> {
+            Some((2, 3))
+        }
+    }
+
+    // Island where the herbivores of (1, 1) migrate into the lake.
+    fn flooded() -> Island<'static> {
+        let mut isl = Island::new(geography(), 5).unwrap();
+        isl.add_population(vec![((1, 1), Herbivore, 20)]).unwrap();
+        isl.migration.insert(Herbivore, Arc::new(IntoWater));
+        isl
+    }
+
+    #[test]
+    fn test_detects_animals_on_water() {
+        let mut isl = flooded();
+        isl.migrate();
+        let drowned = isl.cells()[&(2, 3)].animals[&Herbivore].len();
+        assert!(drowned > 0);
+        let violations = check(&isl, 20, &Counts::default());
+        assert_eq!(violations, vec![Violation::Water { cell: (2, 3), animals: drowned }]);
+    }
+
+    #[test]
+    fn test_detects_unbalanced_totals() {
+        let mut isl = Island::new(geography(), 5).unwrap();
+        isl.add_population(vec![((1, 1), Herbivore, 10)]).unwrap();
+        let counts = Counts { births: 2, ..Counts::default() };
+        let violations = check(&isl, 10, &counts);
+        assert_eq!(violations, vec![Violation::Total {
+            before: 10, after: 10, births: 2, kills: 0, deaths: 0, harvested: 0
+        }]);
+        assert!(violations[0].to_string().contains("from 10 to 10"));
+    }
+
+    #[test]
+    fn test_checking_reports_violations() {
+        let mut isl = flooded();
+        isl.checking = true;
+        let Err(Error::Invariant(report)) = isl.yearly_cycle() else {
+            panic!("Expected an invariant violation")
+        };
+        assert_eq!(report.phase, Phase::Migrate);
+        assert!(report.to_string().contains("animals on water"));
+    }
+}
@@ -1,73 +1,203 @@
-#[path = "../src/animals.rs"] mod animals;
-#[path = "../src/island.rs"] mod island;
-
 #[cfg(test)]
 mod tests {
-    use crate::animals::Species::{Carnivore, Herbivore};
-    use crate::island::*;
-    use std::collections::HashMap;
-    use rand::thread_rng;
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore, Omnivore, Scavenger};
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::vegetation::*;
+    use indexmap::IndexMap;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn grass(f_max: f32, fodder: f32) -> Vegetation {
+        let mut vegetation = Vegetation::default();
+        vegetation.plant(Plant::Grass, f_max);
+        vegetation.stands.get_mut(&Plant::Grass).unwrap().fodder = fodder;
+        vegetation
+    }
 
     #[test]
     fn test_new_island() {
-        let mut rng = thread_rng();
         let geography: Vec<&str> = vec![
             "WWW",
             "WLW",
             "WLW",
             "WWW"
         ];
-        let isl = Island::new(geography, &mut rng);
+        let isl = Island::new(geography, 1).unwrap();
         assert_eq!(isl.year, 0);
-        assert_eq!(isl.inhabited.len(), 0);
+        assert_eq!(isl.inhabited().len(), 0);
     }
 
     #[test]
     fn test_add_population() {
-        let mut rng = thread_rng();
         let geography: Vec<&str> = vec![
             "WWW",
             "WLW",
             "WLW",
             "WWW"
         ];
-        let mut isl = Island::new(geography, &mut rng);
+        let mut isl = Island::new(geography, 1).unwrap();
         isl.add_population(vec![
             ((1, 1), Herbivore, 10),
             ((1, 1), Carnivore, 2)
-        ]);
-        assert_eq!(isl.inhabited.len(), 1);
+        ]).unwrap();
+        assert_eq!(isl.inhabited(), &[(1, 1)]);
+        assert_eq!(isl.animals().0[&Herbivore], 10);
+        assert_eq!(isl.animals().0[&Carnivore], 2);
     }
 
     #[test]
     fn test_yearly_cycle() {
-        let mut rng = thread_rng();
         let geography: Vec<&str> = vec![
             "WWW",
             "WLW",
             "WLW",
             "WWW"
         ];
-        let mut isl = Island::new(geography, &mut rng);
+        let mut isl = Island::new(geography, 1).unwrap();
         isl.add_population(vec![
             ((1, 1), Herbivore, 10),
             ((1, 1), Carnivore, 2)
-        ]);
-        isl.yearly_cycle();
+        ]).unwrap();
+        isl.yearly_cycle().unwrap();
         assert_eq!(isl.year, 1);
     }
 
     #[test]
     fn test_cell_grow_fodder() {
         let mut cell = Cell {
-            f_max: 300,
-            fodder: 200,
-            animals: HashMap::from([
+            terrain: b'H',
+            vegetation: grass(300.0, 200.0),
+            carcass: 0.0,
+            dead: Vec::new(),
+            animals: IndexMap::from([
                 (Herbivore, Vec::new()),
                 (Carnivore, Vec::new())
             ])
         };
-        cell.grow_fodder();
-        assert!(cell.fodder > 200);
+        cell.grow_fodder(&mut StdRng::seed_from_u64(1));
+        assert!(cell.fodder() > 200.0);
+        assert!(cell.fodder() <= cell.vegetation.f_max());
+    }
+
+    #[test]
+    fn test_inhabited_matches_cells() {
+        let geography: Vec<&str> = vec![
+            "WWWWW",
+            "WLLHW",
+            "WLHLW",
+            "WWWWW"
+        ];
+        let mut isl = Island::new(geography, 7).unwrap();
+        isl.add_population(vec![
+            ((1, 1), Herbivore, 50),
+            ((2, 3), Carnivore, 5)
+        ]).unwrap();
+        for _ in 0..10 {
+            isl.yearly_cycle().unwrap();
+            let occupied: Vec<(usize, usize)> = isl.cells().iter()
+                .filter(|(_, cell)| !cell.is_empty())
+                .map(|(coordinate, _)| *coordinate)
+                .collect();
+            assert_eq!(isl.inhabited(), occupied.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_omnivores() {
+        let geography: Vec<&str> = vec![
+            "WWWW",
+            "WLLW",
+            "WLHW",
+            "WWWW"
+        ];
+        // Fodder and herbivores left in a cell after a year of feeding.
+        let feed = |omnivores: u16| {
+            let mut isl = Island::new(geography.clone(), 1).unwrap();
+            isl.add_population(vec![
+                ((1, 1), Herbivore, 5),
+                ((1, 1), Omnivore, omnivores)
+            ]).unwrap();
+            isl.feed().unwrap();
+            let cell = &isl.cells()[&(1, 1)];
+            (cell.fodder(), cell.animals[&Herbivore].len())
+        };
+        // Omnivores both graze and hunt in the same year.
+        let (fodder, herbivores) = feed(0);
+        let (grazed, hunted) = feed(20);
+        assert!(grazed < fodder);
+        assert!(hunted < herbivores);
+
+        let mut isl = Island::new(geography, 1).unwrap();
+        isl.checking = true;
+        isl.add_population(vec![
+            ((1, 1), Herbivore, 50),
+            ((1, 1), Omnivore, 10),
+            ((2, 2), Omnivore, 10)
+        ]).unwrap();
+        for _ in 0..20 {
+            isl.yearly_cycle().unwrap();
+        }
+        assert!(isl.animals().0[&Omnivore] > 0);
+    }
+
+    #[test]
+    fn test_carcass_decays_into_fodder() {
+        let mut cell = Cell {
+            terrain: b'H',
+            vegetation: grass(300.0, 200.0),
+            carcass: 40.0,
+            dead: Vec::new(),
+            animals: IndexMap::new(),
+        };
+        cell.decay(0.5);
+        assert!(cell.carcass > 0.0 && cell.carcass < 40.0);
+        assert_eq!(cell.fodder(), 200.0 + 0.5 * (40.0 - cell.carcass));
+    }
+
+    #[test]
+    fn test_scavengers() {
+        let geography: Vec<&str> = vec![
+            "WWWW",
+            "WLLW",
+            "WLHW",
+            "WWWW"
+        ];
+        // Fodder of a cell at the end of a year with carnivores, and its carcass after feeding
+        // the next year.
+        let run = |enrichment: f32, scavengers: u16| {
+            let mut isl = Island::new(geography.clone(), 3).unwrap();
+            isl.enrichment = enrichment;
+            isl.add_population(vec![
+                ((1, 1), Herbivore, 60),
+                ((1, 1), Carnivore, 10)
+            ]).unwrap();
+            isl.feed().unwrap();
+            isl.aging().unwrap();
+            let fodder = isl.cells()[&(1, 1)].fodder();
+            isl.add_population(vec![((1, 1), Scavenger, scavengers)]).unwrap();
+            isl.feed().unwrap();
+            (fodder, isl.cells()[&(1, 1)].carcass)
+        };
+        // The decaying carcass feeds the grazed land, and scavengers eat what is left of it.
+        let (fodder, carcass) = run(0.0, 0);
+        let (enriched, _) = run(0.5, 0);
+        let (_, scavenged) = run(0.0, 10);
+        assert!(enriched > fodder);
+        assert!(carcass > 0.0);
+        assert!(scavenged < carcass);
+
+        let mut isl = Island::new(geography, 3).unwrap();
+        isl.checking = true;
+        isl.enrichment = 0.5;
+        isl.add_population(vec![
+            ((1, 1), Herbivore, 60),
+            ((1, 1), Carnivore, 10),
+            ((1, 1), Scavenger, 10)
+        ]).unwrap();
+        for _ in 0..20 {
+            isl.yearly_cycle().unwrap();
+        }
+        assert!(isl.cells().values().any(|cell| cell.carcass > 0.0));
+        assert!(isl.animals().0[&Scavenger] > 0);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use indexmap::IndexMap;
+    use ecosystem_rust::animals::Health;
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore};
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::migration::*;
+
+    const GEOGRAPHY: [&str; 11] = [
+        "WWWWWWWWWWW",
+        "WLLLLLLLLLW",
+        "WLLLLLLLLLW",
+        "WLLLLLLLLLW",
+        "WLLLLLLLLLW",
+        "WLLLLLLLLLW",
+        "WLLLLLLLLLW",
+        "WLLLLLLLLLW",
+        "WLLLLLLLLLW",
+        "WLLLLLLLLLW",
+        "WWWWWWWWWWW",
+    ];
+
+    fn island(seed: u64) -> Island<'static> {
+        let mut isl = Island::new(GEOGRAPHY.to_vec(), seed).unwrap();
+        isl.add_population(vec![
+            ((5, 5), Herbivore, 200),
+            ((5, 5), Carnivore, 200)
+        ]).unwrap();
+        isl
+    }
+
+    #[test]
+    fn test_migration_conserves_animals() {
+        for seed in 0..10 {
+            let mut isl = island(seed);
+            for _ in 0..5 {
+                let (before, _) = isl.animals();
+                isl.migrate();
+                let (after, _) = isl.animals();
+                assert_eq!(before, after);
+            }
+        }
+    }
+
+    #[test]
+    fn test_migration_conserves_animals_in_parallel() {
+        let mut isl = island(1);
+        isl.parallel = true;
+        let (before, _) = isl.animals();
+        isl.migrate();
+        let (after, _) = isl.animals();
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_animals_move_at_most_once() {
+        // Herbivores fill a corridor whose cells are handled from left to right, and the ones in
+        // the first cell are marked by infecting them. A herbivore moves one cell at a time, so
+        // the marked ones can at most reach the second cell.
+        let corridor = ["WWWWWWWWWWW", "WLLLLLLLLLW", "WWWWWWWWWWW"];
+        for seed in 0..10 {
+            let mut isl = Island::new(corridor.to_vec(), seed).unwrap();
+            isl.add_population((1..10).map(|j| ((1, j), Herbivore, 50)).collect()).unwrap();
+            isl.infect(vec![((1, 1), Herbivore, 50)]).unwrap();
+            isl.migrate();
+
+            let marked: IndexMap<usize, usize> = (1..10)
+                .map(|j| (j, isl.cells()[&(1, j)].animals[&Herbivore].iter()
+                    .filter(|animal| animal.health == Health::Infected)
+                    .count()))
+                .collect();
+            assert!(marked[&2] > 0);
+            assert!(marked.iter().all(|(j, n)| *j <= 2 || *n == 0), "{:?}", marked);
+            assert_eq!(marked.values().sum::<usize>(), 50);
+        }
+    }
+
+    #[test]
+    fn test_strategies_conserve_animals() {
+        let strategies: Vec<Arc<dyn MigrationStrategy>> = vec![
+            Arc::new(BioSim), Arc::new(Propensity), Arc::new(RandomWalk), Arc::new(Gradient)
+        ];
+        for strategy in strategies {
+            let mut isl = island(4);
+            isl.migration.insert(Herbivore, strategy.clone());
+            isl.migration.insert(Carnivore, strategy);
+            let (before, _) = isl.animals();
+            isl.migrate();
+            let (after, _) = isl.animals();
+            assert_eq!(before, after);
+        }
+    }
+
+    #[test]
+    fn test_species_without_strategy_migrate() {
+        let mut isl = island(7);
+        isl.migration = IndexMap::from([(Herbivore, Arc::new(BioSim) as Arc<dyn MigrationStrategy>)]);
+        let (before, _) = isl.animals();
+        isl.migrate();
+        let (after, placement) = isl.animals();
+        assert_eq!(before, after);
+        assert!(placement.get(&(5, 5)).map_or(0, |species| species[&Carnivore]) < 200);
+    }
+
+    #[test]
+    fn test_biosim_moves_to_adjacent_cells() {
+        let mut isl = island(5);
+        isl.migration.insert(Carnivore, Arc::new(BioSim));
+        isl.migrate();
+
+        let (_, placement) = isl.animals();
+        for ((x, y), species) in placement.iter() {
+            if species[&Carnivore] > 0 {
+                assert!(x.abs_diff(5) + y.abs_diff(5) <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gradient_leaves_crowded_cell() {
+        let mut isl = island(6);
+        isl.migration.insert(Herbivore, Arc::new(Gradient));
+        isl.migrate();
+
+        // Every neighbour has more fodder per herbivore than the crowded origin.
+        let (_, placement) = isl.animals();
+        assert!(placement.get(&(5, 5)).map_or(0, |species| species[&Herbivore]) < 200);
+    }
+}
@@ -0,0 +1,110 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore};
+    use ecosystem_rust::herd::Layout;
+    use ecosystem_rust::mortality::*;
+    use indexmap::IndexMap;
+    use crate::common::island;
+
+    #[test]
+    fn test_hazards() {
+        assert_eq!(Hazard::None.probability(10), 0.0);
+
+        let gompertz = Hazard::Gompertz { a: 0.01, b: 0.3 };
+        assert!(gompertz.probability(1) < gompertz.probability(5));
+        assert!(gompertz.probability(5) < gompertz.probability(20));
+
+        // A Weibull hazard of shape 1 is constant.
+        let weibull = Hazard::Weibull { shape: 1.0, scale: 10.0 };
+        for age in 1..20 {
+            assert!((weibull.probability(age) - (1.0 - (-0.1f32).exp())).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_histogram_summaries() {
+        let ages = [0, 2, 1, 0, 1];
+        assert_eq!(mean_age(&ages), Some((2.0 + 2.0 + 4.0) / 4.0));
+        assert_eq!(quantile(&ages, 0.5), Some(1));
+        assert_eq!(quantile(&ages, 1.0), Some(4));
+        assert_eq!(mean_age(&[]), None);
+    }
+
+    #[test]
+    fn test_maximum_age() {
+        let mut isl = island(1);
+        isl.add_population(vec![((1, 1), Herbivore, 100)]).unwrap();
+        isl.mortality.insert(Herbivore, Mortality { max_age: Some(3), ..Mortality::default() });
+        for _ in 0..10 {
+            isl.yearly_cycle().unwrap();
+            assert!(isl.cells().values()
+                .flat_map(|cell| cell.animals[&Herbivore].iter())
+                .all(|animal| animal.age <= 3));
+        }
+        let ages = &isl.ages_at_death()[&Herbivore];
+        assert!(ages.len() <= 5);
+        assert!(ages.iter().sum::<u64>() > 0);
+    }
+
+    #[test]
+    fn test_hazard_shortens_lives() {
+        let mean = |mortality: Mortality| {
+            let mut isl = island(2);
+            isl.add_population(vec![
+                ((1, 1), Herbivore, 100),
+                ((1, 1), Carnivore, 10)
+            ]).unwrap();
+            isl.mortality.insert(Carnivore, mortality);
+            for _ in 0..30 {
+                isl.yearly_cycle().unwrap();
+            }
+            mean_age(&isl.ages_at_death()[&Carnivore]).unwrap()
+        };
+        let harsh = Mortality { hazard: Hazard::Gompertz { a: 0.05, b: 0.5 }, max_age: None };
+        assert!(mean(harsh) < mean(Mortality::default()));
+    }
+
+    #[test]
+    fn test_layouts_agree() {
+        let ages = |layout: Layout| {
+            let mut isl = island(3);
+            isl.add_population(vec![((1, 1), Herbivore, 100)]).unwrap();
+            isl.layout = layout;
+            isl.mortality.insert(Herbivore, Mortality {
+                hazard: Hazard::Weibull { shape: 2.0, scale: 6.0 },
+                max_age: Some(12),
+            });
+            for _ in 0..15 {
+                isl.yearly_cycle().unwrap();
+            }
+            isl.ages_at_death().clone()
+        };
+        assert_eq!(ages(Layout::Structs), ages(Layout::Columns));
+    }
+
+    #[test]
+    fn test_invalid_hazard() {
+        let mut isl = island(4);
+        isl.add_population(vec![((1, 1), Herbivore, 10)]).unwrap();
+        isl.mortality.insert(Herbivore, Mortality { hazard: Hazard::Gompertz { a: 0.1, b: 0.0 }, max_age: None });
+        assert!(isl.aging().is_err());
+    }
+
+    #[test]
+    fn test_species_without_mortality() {
+        let mut isl = island(5);
+        isl.add_population(vec![
+            ((1, 1), Herbivore, 50),
+            ((1, 1), Carnivore, 10)
+        ]).unwrap();
+        isl.mortality = IndexMap::from([(Herbivore, Mortality { max_age: Some(3), ..Mortality::default() })]);
+        for _ in 0..10 {
+            isl.yearly_cycle().unwrap();
+        }
+        assert!(isl.cells().values()
+            .flat_map(|cell| cell.animals[&Herbivore].iter())
+            .all(|animal| animal.age <= 3));
+    }
+}
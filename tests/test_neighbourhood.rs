@@ -0,0 +1,14 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::neighbourhood::Metric::{Chebyshev, Euclidean, Manhattan};
+
+    #[test]
+    fn test_metric_within() {
+        assert!(Euclidean.within((5, 5), (3, 4), 3));
+        assert!(!Euclidean.within((5, 5), (2, 3), 3));
+        assert!(Manhattan.within((5, 5), (3, 4), 3));
+        assert!(!Manhattan.within((5, 5), (3, 3), 3));
+        assert!(Chebyshev.within((5, 5), (2, 2), 3));
+        assert!(!Chebyshev.within((5, 5), (1, 5), 3));
+    }
+}
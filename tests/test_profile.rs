@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore};
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::streams::Phase;
+
+    #[test]
+    fn test_counts_balance_population() {
+        let geography: Vec<&str> = vec![
+            "WWWWWW",
+            "WLLHHW",
+            "WLLHHW",
+            "WWWWWW",
+        ];
+        let mut isl = Island::new(geography, 3).unwrap();
+        isl.profiling = true;
+        isl.add_population(vec![
+            ((1, 1), Herbivore, 40),
+            ((1, 1), Carnivore, 4)
+        ]).unwrap();
+        for _ in 0..20 {
+            isl.yearly_cycle().unwrap();
+        }
+
+        let (n, _) = isl.animals();
+        let counts = isl.profile.counts;
+        assert_eq!(
+            (n[&Herbivore] + n[&Carnivore]) as u64,
+            44 + counts.births - counts.kills - counts.deaths
+        );
+        assert!(counts.migrations <= counts.migration_attempts);
+        assert_eq!(isl.profile.years, 20);
+        assert_eq!(isl.profile.time.len(), 4);
+        assert!(isl.profile.time.contains_key(&Phase::Migrate));
+    }
+
+    #[test]
+    fn test_profiling_is_off_by_default() {
+        let mut isl = Island::new(vec!["WWW", "WLW", "WWW"], 3).unwrap();
+        isl.add_population(vec![((1, 1), Herbivore, 10)]).unwrap();
+        isl.yearly_cycle().unwrap();
+        assert_eq!(isl.profile.years, 0);
+        assert!(isl.profile.time.is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::*;
+    use ecosystem_rust::island::*;
+    use proptest::prelude::*;
+
+    fn species() -> impl Strategy<Value = Species> {
+        prop_oneof![Just(Species::Herbivore), Just(Species::Carnivore), Just(Species::Omnivore)]
+    }
+
+    fn animal() -> impl Strategy<Value = Animal> {
+        (species(), 0.0f32..200.0, 0u32..200).prop_map(|(species, weight, age)| {
+            let mut animal = Animal { species, weight, age, fitness: 0.0, health: Health::Susceptible };
+            animal.calculate_fitness();
+            animal
+        })
+    }
+
+    fn geography() -> Vec<&'static str> {
+        vec![
+            "WWWWWWW",
+            "WLLHLLW",
+            "WLHHMLW",
+            "WLLLLHW",
+            "WWWWWWW",
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn fitness_is_a_probability(species in species(), weight in -50.0f32..1000.0, age in 0u32..500) {
+            let mut animal = Animal { species, weight, age, fitness: 0.0, health: Health::Susceptible };
+            animal.calculate_fitness();
+            prop_assert!((0.0..=1.0).contains(&animal.fitness));
+        }
+
+        #[test]
+        fn weight_is_never_negative_after_aging(mut animal in animal(), years in 1usize..50) {
+            for _ in 0..years {
+                animal.aging();
+                animal.lose_weight_year();
+                animal.calculate_fitness();
+                prop_assert!(animal.weight >= 0.0);
+                prop_assert!((0.0..=1.0).contains(&animal.fitness));
+            }
+        }
+
+        #[test]
+        fn eating_never_loses_weight(mut animal in animal(), food in 0.0f32..100.0) {
+            let before = animal.weight;
+            animal.eat(food);
+            prop_assert!(animal.weight >= before);
+        }
+
+        #[test]
+        fn grazing_is_bounded(mut animal in animal(), available in 0.0f32..1000.0) {
+            let parameters = animal.species.parameters();
+            prop_assume!(parameters.grazing > 0.0);
+            let eaten = animal.graze(available).unwrap();
+            prop_assert!(eaten <= available);
+            prop_assert!(eaten <= parameters.hunger * parameters.grazing);
+        }
+
+        #[test]
+        fn migration_conserves_animals(seed in any::<u64>(), herbivores in 1u16..100, carnivores in 0u16..20) {
+            let mut isl = Island::new(geography(), seed).unwrap();
+            isl.add_population(vec![
+                ((2, 2), Species::Herbivore, herbivores),
+                ((2, 2), Species::Carnivore, carnivores),
+            ]).unwrap();
+            for _ in 0..5 {
+                let (before, _) = isl.animals();
+                isl.migrate();
+                let (after, _) = isl.animals();
+                prop_assert_eq!(before, after);
+                isl.year += 1;
+            }
+        }
+
+        #[test]
+        fn aging_leaves_no_dead_animals(seed in any::<u64>(), herbivores in 1u16..100) {
+            let mut isl = Island::new(geography(), seed).unwrap();
+            isl.add_population(vec![((1, 1), Species::Herbivore, herbivores)]).unwrap();
+            for _ in 0..3 {
+                isl.yearly_cycle().unwrap();
+                for cell in isl.cells().values() {
+                    for animal in cell.animals.values().flatten() {
+                        prop_assert!(animal.weight > 0.0);
+                    }
+                }
+            }
+        }
+    }
+}
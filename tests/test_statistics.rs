@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::*;
+    use ecosystem_rust::validation::normal_cdf;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn lognormal_cdf(x: f64, mean: f64, std: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0
+        }
+        normal_cdf((x.ln() - mean) / std)
+    }
+
+    // Kolmogorov-Smirnov statistic of `samples` against `cdf`.
+    fn kolmogorov_smirnov<F: Fn(f64) -> f64>(mut samples: Vec<f64>, cdf: F) -> f64 {
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = samples.len() as f64;
+        samples.iter().enumerate()
+            .map(|(i, &x)| {
+                let f = cdf(x);
+                (f - i as f64 / n).abs().max((((i + 1) as f64) / n - f).abs())
+            })
+            .fold(0.0, f64::max)
+    }
+
+    // Log-space mean and standard deviation of the log-normal distribution with the mean `w_birth`
+    // and standard deviation `sigma_birth` of `p`, independently of `birth_mean` and `birth_std`.
+    fn log_parameters(p: &Parameters) -> (f64, f64) {
+        let (w, sigma) = (p.w_birth as f64, p.sigma_birth as f64);
+        let variance = (1.0 + sigma * sigma / (w * w)).ln();
+        (w.ln() - variance / 2.0, variance.sqrt())
+    }
+
+    #[test]
+    fn test_birthweight_parameters() {
+        // The log-space parameters must give the configured mean and standard deviation.
+        for species in Species::ALL {
+            let p = species.parameters();
+            let (mean, std) = (p.birth_mean as f64, p.birth_std as f64);
+            let expected = (mean + std * std / 2.0).exp();
+            let variance = ((std * std).exp() - 1.0) * (2.0 * mean + std * std).exp();
+            assert!((expected - p.w_birth as f64).abs() < 1e-4);
+            assert!((variance.sqrt() - p.sigma_birth as f64).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_birthweight_is_lognormal() {
+        let n = 5000;
+        for species in Species::ALL {
+            let p = species.parameters();
+            let mut rng = StdRng::seed_from_u64(2024);
+            let samples: Vec<f64> = (0..n)
+                .map(|_| birthweight(species, &mut rng).unwrap() as f64)
+                .collect();
+            // The sample moments are within four standard errors of `w_birth` and `sigma_birth`.
+            let mean = samples.iter().sum::<f64>() / n as f64;
+            let sd = (samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt();
+            let error = p.sigma_birth as f64 / (n as f64).sqrt();
+            assert!((mean - p.w_birth as f64).abs() < 4.0 * error, "{}: mean {}", species, mean);
+            assert!((sd - p.sigma_birth as f64).abs() < 0.1 * p.sigma_birth as f64, "{}: sd {}", species, sd);
+
+            let (log_mean, log_std) = log_parameters(p);
+            let d = kolmogorov_smirnov(samples, |x| lognormal_cdf(x, log_mean, log_std));
+
+            // Critical value at the 0.1% significance level.
+            let critical = 1.95 / (n as f64).sqrt();
+            assert!(d < critical, "{}: D = {} >= {}", species, d, critical);
+        }
+    }
+
+    #[test]
+    fn test_kolmogorov_smirnov_rejects_wrong_distribution() {
+        let n = 5000;
+        let p = &Parameters::HERBIVORE;
+        let mut rng = StdRng::seed_from_u64(2024);
+        let samples: Vec<f64> = (0..n)
+            .map(|_| birthweight(Species::Herbivore, &mut rng).unwrap() as f64 * 1.2)
+            .collect();
+        let (log_mean, log_std) = log_parameters(p);
+        let d = kolmogorov_smirnov(samples, |x| lognormal_cdf(x, log_mean, log_std));
+        assert!(d > 1.95 / (n as f64).sqrt());
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore};
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::streams::*;
+    use rand::Rng;
+
+    fn run(seed: u64, parallel: bool) -> Vec<(u32, u32)> {
+        let geography: Vec<&str> = vec![
+            "WWWWWWW",
+            "WLLLHHW",
+            "WLLLHHW",
+            "WHHLLLW",
+            "WWWWWWW",
+        ];
+        let mut isl = Island::new(geography, seed).unwrap();
+        isl.parallel = parallel;
+        isl.add_population(vec![
+            ((2, 2), Herbivore, 50),
+            ((2, 2), Carnivore, 5)
+        ]).unwrap();
+        (0..30)
+            .map(|_| {
+                isl.yearly_cycle().unwrap();
+                let (n, _) = isl.animals();
+                (n[&Herbivore], n[&Carnivore])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_seeded_runs_are_reproducible() {
+        assert_eq!(run(7, false), run(7, false));
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        assert_eq!(run(7, false), run(7, true));
+        assert_eq!(run(42, false), run(42, true));
+    }
+
+    #[test]
+    fn test_consecutive_seeds_do_not_share_streams() {
+        let draw = |seed, year| stream(seed, year, (1, 1), Phase::Feed).gen::<u64>();
+        assert_ne!(draw(0, 1), draw(1, 0));
+        assert_ne!(draw(2, 3), draw(3, 2));
+    }
+}
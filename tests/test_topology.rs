@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::Species::{Carnivore, Herbivore};
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::neighbourhood::Metric::{self, Chebyshev, Euclidean, Manhattan};
+    use ecosystem_rust::simulation::*;
+    use ecosystem_rust::topology::*;
+    use ecosystem_rust::Error;
+
+    // Bounded square grid of 20 by 20 cells, with cells within stride measured by `metric`.
+    fn square(metric: Metric) -> Topology {
+        Topology { metric, ..Topology::new(Grid::Square4, false, (20, 20)).unwrap() }
+    }
+
+    #[test]
+    fn test_within_sizes() {
+        for (metric, radius, n) in [
+            (Euclidean, 1, 4), (Manhattan, 1, 4), (Chebyshev, 1, 8),
+            (Euclidean, 2, 12), (Manhattan, 2, 12), (Chebyshev, 2, 24),
+            (Euclidean, 3, 28), (Manhattan, 3, 24), (Chebyshev, 3, 48),
+        ] {
+            assert_eq!(square(metric).within((10, 10), radius).len(), n, "{:?} {}", metric, radius);
+        }
+    }
+
+    #[test]
+    fn test_within_excludes_origin() {
+        for metric in [Euclidean, Manhattan, Chebyshev] {
+            assert!(!square(metric).within((10, 10), 3).contains(&(10, 10)));
+        }
+    }
+
+    #[test]
+    fn test_within_is_symmetric() {
+        for metric in [Euclidean, Manhattan, Chebyshev] {
+            let cells = square(metric).within((10, 10), 3);
+            let north = cells.iter().filter(|(i, _)| *i < 10).count();
+            let south = cells.iter().filter(|(i, _)| *i > 10).count();
+            let west = cells.iter().filter(|(_, j)| *j < 10).count();
+            let east = cells.iter().filter(|(_, j)| *j > 10).count();
+            assert_eq!(north, south);
+            assert_eq!(west, east);
+            assert_eq!(north, west);
+        }
+    }
+
+    #[test]
+    fn test_within_respects_distance() {
+        let cells = square(Euclidean).within((10, 10), 3);
+        assert!(cells.contains(&(7, 10)));
+        assert!(cells.contains(&(8, 8)));
+        assert!(!cells.contains(&(7, 9)));
+        assert!(!cells.contains(&(7, 7)));
+        assert!(!cells.contains(&(13, 13)));
+    }
+
+    #[test]
+    fn test_within_is_clipped_to_grid() {
+        let cells = square(Chebyshev).within((0, 0), 2);
+        assert_eq!(cells.len(), 8);
+        let cells = square(Chebyshev).within((19, 19), 2);
+        assert_eq!(cells.len(), 8);
+        assert!(cells.iter().all(|&(i, j)| i < 20 && j < 20));
+    }
+
+    #[test]
+    fn test_adjacent_cells() {
+        for (grid, n) in [(Grid::Square4, 4), (Grid::Square8, 8), (Grid::Hexagonal, 6)] {
+            let topology = Topology::new(grid, false, (10, 10)).unwrap();
+            assert_eq!(topology.adjacent((4, 4)).len(), n, "{:?}", grid);
+            assert_eq!(topology.adjacent((5, 5)).len(), n, "{:?}", grid);
+        }
+    }
+
+    #[test]
+    fn test_hexagonal_offset_rows() {
+        let topology = Topology::new(Grid::Hexagonal, false, (10, 10)).unwrap();
+        let mut even = topology.adjacent((4, 4));
+        even.sort();
+        assert_eq!(even, vec![(3, 3), (3, 4), (4, 3), (4, 5), (5, 3), (5, 4)]);
+        let mut odd = topology.adjacent((5, 4));
+        odd.sort();
+        assert_eq!(odd, vec![(4, 4), (4, 5), (5, 3), (5, 5), (6, 4), (6, 5)]);
+    }
+
+    #[test]
+    fn test_adjacency_is_symmetric() {
+        for grid in [Grid::Square4, Grid::Square8, Grid::Hexagonal] {
+            for toroidal in [false, true] {
+                let topology = Topology::new(grid, toroidal, (6, 7)).unwrap();
+                for i in 0..6 {
+                    for j in 0..7 {
+                        for other in topology.adjacent((i, j)) {
+                            assert!(topology.adjacent(other).contains(&(i, j)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_hexagonal_rings() {
+        let topology = Topology::new(Grid::Hexagonal, false, (20, 20)).unwrap();
+        assert_eq!(topology.within((10, 10), 2).len(), 18);
+        assert_eq!(topology.within((11, 10), 3).len(), 36);
+    }
+
+    #[test]
+    fn test_toroidal_wraps_around() {
+        let topology = Topology::new(Grid::Square4, true, (5, 5)).unwrap();
+        let mut corner = topology.adjacent((0, 0));
+        corner.sort();
+        assert_eq!(corner, vec![(0, 1), (0, 4), (1, 0), (4, 0)]);
+
+        let topology = Topology::new(Grid::Hexagonal, true, (6, 6)).unwrap();
+        assert_eq!(topology.adjacent((0, 0)).len(), 6);
+        assert_eq!(topology.within((0, 0), 2).len(), 18);
+    }
+
+    #[test]
+    fn test_toroidal_island_without_border() {
+        let geography: Vec<&str> = vec![
+            "LLLL",
+            "LLLL",
+            "LLLL",
+            "LLLL",
+        ];
+        let mut isl = Island::with_topology(geography, 1, Grid::Square8, true).unwrap();
+        isl.add_population(vec![
+            ((0, 0), Herbivore, 100),
+            ((0, 0), Carnivore, 100)
+        ]).unwrap();
+        let (before, _) = isl.animals();
+        isl.migrate();
+        let (after, placement) = isl.animals();
+        assert_eq!(before, after);
+        assert!(placement.contains_key(&(3, 3)) || placement.contains_key(&(0, 3)));
+    }
+
+    #[test]
+    fn test_bounded_island_needs_border() {
+        assert!(matches!(
+            Island::new(vec!["LLL", "LLL", "LLL"], 1),
+            Err(Error::InvalidGeography(_))
+        ));
+        assert!(Topology::new(Grid::Hexagonal, true, (5, 6)).is_err());
+    }
+
+    #[test]
+    fn test_only_odd_hexagonal_rows_are_indented() {
+        let indented = vec!["WWWWW", " WLLLW", "WLLLW", " WWWWW"];
+        assert!(Island::with_topology(indented.clone(), 1, Grid::Hexagonal, false).is_ok());
+        assert!(matches!(
+            Island::with_topology(indented, 1, Grid::Square4, false),
+            Err(Error::InvalidGeography(_))
+        ));
+        assert!(Island::with_topology(vec![" WWWW", "WLLLW", "WWWWW"], 1, Grid::Hexagonal, false).is_err());
+    }
+
+    #[test]
+    fn test_hexagonal_map_is_drawn() {
+        let geography: Vec<&str> = vec![
+            "WWWWWW",
+            " WLLHWW",
+            "WLHLLW",
+            " WWWWWW",
+        ];
+        let mut sim = Simulation::with_topology(geography, 1, Grid::Hexagonal, false, "unused.png").unwrap();
+        sim.add_population(vec![((1, 2), Herbivore, 10)]).unwrap();
+        let path = std::env::temp_dir().join("ecosystem_hexagonal_map.png");
+        sim.map(path.to_str().unwrap()).unwrap();
+        assert!(path.exists());
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use ecosystem_rust::animals::Species;
+    use ecosystem_rust::error::Error;
+    use ecosystem_rust::validation::*;
+
+    fn configuration(herbivores: u16) -> Configuration<'static> {
+        Configuration {
+            geography: vec![
+                "WWWWW",
+                "WLLHW",
+                "WLLLW",
+                "WWWWW",
+            ],
+            population: vec![
+                ((1, 1), Species::Herbivore, herbivores),
+                ((1, 1), Species::Carnivore, 5)
+            ],
+            years: 20,
+        }
+    }
+
+    #[test]
+    fn test_read_reference() {
+        let text = "replicate,year,herbivores,carnivores\n0,0,10,2\n0,1,12,3\n1,0,10,2\n1,1,8,0\n";
+        let reference = read_reference(text).unwrap();
+        assert_eq!(reference, vec![
+            Replicate { herbivores: vec![10, 12], carnivores: vec![2, 3] },
+            Replicate { herbivores: vec![10, 8], carnivores: vec![2, 0] },
+        ]);
+        assert!(reference[1].extinct(Species::Carnivore));
+        assert!(!reference[1].extinct(Species::Herbivore));
+
+        // Without the header, the first line would be lost.
+        assert!(matches!(read_reference("0,0,10,2\n0,1,12,3\n"), Err(Error::InvalidData(_))));
+        assert!(matches!(read_reference(""), Err(Error::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_read_configuration() {
+        let configuration = read_configuration(include_str!("../validate/island.txt")).unwrap();
+        assert_eq!(configuration.geography.len(), 8);
+        assert_eq!(configuration.geography[1], "WWWLHHWWWHHWW");
+        assert_eq!(configuration.population, vec![
+            ((2, 2), Species::Herbivore, 100),
+            ((2, 2), Species::Carnivore, 10)
+        ]);
+
+        assert!(read_configuration("population\n1,1,Herbivore,10\n").is_err());
+        assert!(read_configuration("map\nWWW\nWLW\nWWW\npopulation\n1,1,Dragon,10\n").is_err());
+        assert!(read_configuration("WWW\nWLW\nWWW\n").is_err());
+    }
+
+    #[test]
+    fn test_first_peak() {
+        let replicate = Replicate {
+            herbivores: vec![0; 8],
+            carnivores: vec![5, 6, 5, 10, 20, 30, 25, 28],
+        };
+        // The early bump at year 1 is below half of the maximum.
+        assert_eq!(replicate.first_peak(), Some(5));
+    }
+
+    #[test]
+    fn test_statistical_tests() {
+        let (_, p) = welch(&[1.0, 2.0, 3.0, 4.0], &[1.0, 2.0, 3.0, 4.0]);
+        assert!(p > 0.999);
+        let (_, p) = welch(&[1.0, 2.0, 3.0, 4.0], &[101.0, 102.0, 103.0, 104.0]);
+        assert!(p < 1e-6);
+
+        let a: Vec<f64> = (0..100).map(|x| x as f64).collect();
+        let b: Vec<f64> = (0..100).map(|x| x as f64 + 0.5).collect();
+        let c: Vec<f64> = (0..100).map(|x| x as f64 + 60.0).collect();
+        assert!(kolmogorov_smirnov(&a, &b).1 > 0.9);
+        assert_eq!(kolmogorov_smirnov(&a, &a), (0.0, 1.0));
+        assert!(kolmogorov_smirnov(&a, &c).1 < 1e-6);
+
+        assert!(proportions((10, 50), (11, 50)).1 > 0.5);
+        assert!(proportions((0, 50), (40, 50)).1 < 1e-6);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_independent_replicates_agree() {
+        let ours = replicates(&configuration(40), 30, 0).unwrap();
+        let reference = replicates(&configuration(40), 30, 1000).unwrap();
+        let report = compare(&ours, &reference, 0.01);
+        assert!(report.agrees(), "{}", report);
+    }
+
+    #[test]
+    fn test_different_configurations_disagree() {
+        let ours = replicates(&configuration(40), 30, 0).unwrap();
+        let reference = replicates(&configuration(5), 30, 1000).unwrap();
+        let report = compare(&ours, &reference, 0.01);
+        assert!(!report.agrees(), "{}", report);
+    }
+
+    #[test]
+    fn test_seeds_wrap_around() {
+        let wrapped = replicates(&configuration(40), 2, u64::MAX).unwrap();
+        assert_eq!(wrapped[1], replicates(&configuration(40), 1, 0).unwrap()[0]);
+    }
+}
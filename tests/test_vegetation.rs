@@ -0,0 +1,152 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use ecosystem_rust::animals::Species::{self, Carnivore, Herbivore, Omnivore};
+    use ecosystem_rust::growth::*;
+    use ecosystem_rust::island::*;
+    use ecosystem_rust::vegetation::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::common::island;
+
+    fn mixed(grass: f32, shrub: f32) -> Vegetation {
+        let mut vegetation = Vegetation::default();
+        vegetation.plant(Plant::Grass, grass);
+        vegetation.plant(Plant::Shrub, shrub);
+        vegetation
+    }
+
+    #[test]
+    fn test_pasture_depends_on_diet() {
+        let vegetation = mixed(100.0, 100.0);
+        assert_eq!(vegetation.fodder(), 200.0);
+        assert_eq!(vegetation.pasture(&Herbivore), 100.0 + 0.3 * 100.0);
+        assert_eq!(vegetation.pasture(&Omnivore), 100.0 + 0.6 * 100.0);
+        assert_eq!(vegetation.pasture(&Carnivore), 0.0);
+    }
+
+    #[test]
+    fn test_most_palatable_is_cropped_first() {
+        let mut vegetation = mixed(100.0, 100.0);
+        vegetation.crop(&Herbivore, 80.0);
+        assert_eq!(vegetation.stands[&Plant::Grass].fodder, 20.0);
+        assert_eq!(vegetation.stands[&Plant::Shrub].fodder, 100.0);
+
+        vegetation.crop(&Herbivore, 26.0);
+        assert_eq!(vegetation.stands[&Plant::Grass].fodder, 0.0);
+        assert!((vegetation.stands[&Plant::Shrub].fodder - 80.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_shrubs_shade_grass() {
+        let mut alone = mixed(800.0, 0.0);
+        let mut shaded = mixed(800.0, 200.0);
+        for vegetation in [&mut alone, &mut shaded] {
+            vegetation.stands.get_mut(&Plant::Grass).unwrap().fodder = 0.0;
+            vegetation.grow(&mut StdRng::seed_from_u64(1));
+        }
+        assert!(shaded.stands[&Plant::Grass].fodder < alone.stands[&Plant::Grass].fodder);
+        assert!(shaded.stands[&Plant::Grass].fodder <= 800.0 - 0.5 * 200.0);
+    }
+
+    #[test]
+    fn test_diets_partition_vegetation() {
+        let mut isl = island(8);
+        isl.set_vegetation('H', Plant::Shrub, 400.0).unwrap();
+        assert!(isl.set_vegetation('W', Plant::Shrub, 100.0).is_err());
+        assert_eq!(isl.cells()[&(1, 3)].vegetation.stands[&Plant::Shrub].f_max, 400.0);
+        assert_eq!(isl.cells()[&(1, 1)].vegetation.stands[&Plant::Shrub].f_max, 0.0);
+
+        // Grass and shrub fodder left on the highland cell after a year of feeding.
+        let fodder = |grass: f32, population: Vec<((usize, usize), Species, u16)>| {
+            let mut isl = island(8);
+            isl.set_vegetation('H', Plant::Grass, grass).unwrap();
+            isl.set_vegetation('H', Plant::Shrub, 400.0).unwrap();
+            isl.add_population(population).unwrap();
+            isl.feed().unwrap();
+            let stands = &isl.cells()[&(1, 3)].vegetation.stands;
+            (stands[&Plant::Grass].fodder, stands[&Plant::Shrub].fodder)
+        };
+        // Carnivores don't eat plants, but fodder only grows in inhabited cells.
+        let (grass, shrub) = fodder(300.0, vec![((1, 3), Carnivore, 5)]);
+
+        // Herbivores eat grass first, and only turn to the shrubs once the grass is gone.
+        let (grazed, browsed) = fodder(300.0, vec![((1, 3), Herbivore, 2)]);
+        assert!(grazed < grass);
+        assert_eq!(browsed, shrub);
+        let (grazed, browsed) = fodder(300.0, vec![((1, 3), Herbivore, 30)]);
+        assert_eq!(grazed, 0.0);
+        assert!(browsed < shrub);
+
+        // Without grass, omnivores get twice the food out of the shrubs, so the same food takes
+        // half the shrubs that it takes herbivores.
+        let (_, shrub) = fodder(0.0, vec![((1, 3), Carnivore, 5)]);
+        let (_, herbivores) = fodder(0.0, vec![((1, 3), Herbivore, 2)]);
+        let (_, omnivores) = fodder(0.0, vec![((1, 3), Omnivore, 2)]);
+        assert!(((shrub - herbivores) - 2.0 * (shrub - omnivores)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_overgrazing_degrades_and_rest_recovers() {
+        let degradation = Degradation { threshold: 0.5, years: 2, loss: 0.5, rest: 1, recovery: 0.25 };
+        let mut vegetation = mixed(800.0, 0.0);
+        for _ in 0..3 {
+            vegetation.stands.get_mut(&Plant::Grass).unwrap().fodder = 0.0;
+            vegetation.degrade(&degradation, true);
+        }
+        assert_eq!(vegetation.stands[&Plant::Grass].f_max, 400.0);
+        assert_eq!(vegetation.stands[&Plant::Shrub].f_max, 0.0);
+
+        vegetation.degrade(&degradation, false);
+        assert_eq!(vegetation.stands[&Plant::Grass].f_max, 400.0);
+        for _ in 0..5 {
+            vegetation.degrade(&degradation, false);
+        }
+        assert_eq!(vegetation.stands[&Plant::Grass].f_max, 800.0);
+    }
+
+    #[test]
+    fn test_seeds_disperse_from_neighbours() {
+        let geography: Vec<&str> = vec![
+            "WWWWW",
+            "WLLLW",
+            "WWWWW"
+        ];
+        let eaten = |dispersal: Option<Dispersal>| -> f32 {
+            let mut isl = Island::new(geography.clone(), 1).unwrap();
+            isl.set_growth('L', Plant::Grass, Arc::new(Linear { rate: 0.0 })).unwrap();
+            isl.add_population(vec![((1, 2), Herbivore, 200)]).unwrap();
+            isl.feed().unwrap();
+            assert_eq!(isl.cells()[&(1, 2)].fodder(), 0.0);
+
+            isl.dispersal = dispersal;
+            isl.feed().unwrap();
+            isl.cells()[&(1, 2)].animals[&Herbivore].iter().map(|animal| animal.weight).sum()
+        };
+        assert!(eaten(Some(Dispersal { rate: 0.1 })) > eaten(None));
+    }
+
+    #[test]
+    fn test_degrading_island_keeps_invariants() {
+        let mut isl = island(9);
+        isl.dispersal = Some(Dispersal { rate: 0.05 });
+        isl.degradation = Some(Degradation { threshold: 0.5, years: 1, ..Degradation::default() });
+        isl.add_population(vec![((1, 1), Herbivore, 150)]).unwrap();
+        for _ in 0..30 {
+            isl.yearly_cycle().unwrap();
+        }
+        assert!(isl.cells().values().all(|cell| cell.vegetation.f_max() <= 800.0));
+
+        // An invalid degradation is found before any fodder is dispersed or grazed.
+        let mut isl = island(10);
+        isl.dispersal = Some(Dispersal { rate: 0.05 });
+        isl.degradation = Some(Degradation { loss: 2.0, ..Degradation::default() });
+        isl.add_population(vec![((1, 1), Herbivore, 50)]).unwrap();
+        let fodder = |isl: &Island| isl.cells().values().map(|cell| cell.fodder()).collect::<Vec<f32>>();
+        let before = fodder(&isl);
+        assert!(isl.feed().is_err());
+        assert_eq!(fodder(&isl), before);
+    }
+}
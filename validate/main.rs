@@ -0,0 +1,54 @@
+// Cross-validation against the Python implementation of `ecosystem`.
+//
+//     cargo run --release --bin validate -- reference.csv configuration.txt [replicates] [seed]
+//
+// Runs the configuration (map and initial population, see `validation::read_configuration` and
+// `validate/island.txt`) for a number of seeded replicates, and compares the yearly population
+// with reference data exported from Python (see `validation::read_reference` for the format).
+// The reference must be run on the same map, with the same initial population, and sets the
+// number of years.
+
+use std::process::ExitCode;
+use ecosystem_rust::validation::*;
+use ecosystem_rust::{Error, Result};
+
+fn main() -> ExitCode {
+    match validate() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Prints the report, and returns whether the replicates agree with the reference.
+fn validate() -> Result<bool> {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let [reference, configuration, ..] = &arguments[..] else {
+        return Err(Error::InvalidParameter(
+            "usage: validate <reference.csv> <configuration.txt> [replicates] [seed]".to_string()
+        ))
+    };
+    let number = |index: usize, name: &str, default: u64| -> Result<u64> {
+        arguments.get(index).map_or(Ok(default), |argument| argument.parse().map_err(|_| {
+            Error::InvalidParameter(format!("{} must be a whole number, not {}", name, argument))
+        }))
+    };
+    let replicates = number(2, "replicates", 100)?;
+    let seed = number(3, "seed", 0)?;
+
+    let reference = load_reference(reference)?;
+    let text = std::fs::read_to_string(configuration)?;
+    let mut configuration = read_configuration(&text)?;
+    configuration.years = reference.iter()
+        .map(|replicate| replicate.herbivores.len().saturating_sub(1))
+        .min()
+        .unwrap_or(0) as u16;
+
+    let ours = ecosystem_rust::validation::replicates(&configuration, replicates, seed)?;
+    let report = compare(&ours, &reference, 0.05);
+    println!("{}", report);
+    Ok(report.agrees())
+}